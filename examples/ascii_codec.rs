@@ -41,7 +41,7 @@ pub fn encode_bytes_ascii(input: &[u8], per_line: usize) -> String {
 /// Each token must be exactly 3 digits 000–255.
 pub fn decode_bytes_ascii(input: &str) -> Result<Vec<u8>, &'static str> {
     let mut out = Vec::new();
-    for token in input.split(|c| c == ' ' || c == '\n').filter(|t| !t.is_empty()) {
+    for token in input.split([' ', '\n']).filter(|t| !t.is_empty()) {
         if token.len() != 3 {
             return Err("token is not 3 characters");
         }
@@ -21,7 +21,7 @@ fn main() {
 
     println!("Full-byte BitArray:");
     println!("  input bytes: {:02X?}", input_a);
-    println!("  derived key: {:02X?}", key_a);
+    println!("  derived key: {:02X?}", key_a.expose_secret());
 
     // ---------------------------------------------------------------------
     // Example B: Partial-length BitArray (13-bit domain)
@@ -34,7 +34,7 @@ fn main() {
 
     println!("\nPartial (13-bit) BitArray:");
     println!("  input bytes (masked): {:02X?}", input_b);
-    println!("  derived key:          {:02X?}", key_b);
+    println!("  derived key:          {:02X?}", key_b.expose_secret());
 
     // ---------------------------------------------------------------------
     // Example C: UTF‑8 string → BitArray → HKDF‑SHA512
@@ -54,7 +54,7 @@ fn main() {
     println!("\nUTF‑8 → BitArray → HKDF:");
     println!("  UTF‑8 bytes: {:02X?}", utf8);
     println!("  BitArray:    {:02X?}", bits_c.as_bytes());
-    println!("  derived key: {:02X?}", key_c);
+    println!("  derived key: {:02X?}", key_c.expose_secret());
 
     // ---------------------------------------------------------------------
     println!("\nAll HKDF derivations succeeded.");
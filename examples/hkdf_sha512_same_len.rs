@@ -19,7 +19,7 @@ fn main() {
     let key = hkdf_sha512_same_len(input, salt, info);
 
     println!("Input bytes (len={}): {:?}", input.len(), input);
-    println!("Derived HKDF‑SHA512 key (len={}): {:02X?}", key.len(), key);
+    println!("Derived HKDF‑SHA512 key (len={}): {:02X?}", key.len(), key.expose_secret());
 
     // Show determinism: same input → same output
     let key2 = hkdf_sha512_same_len(input, salt, info);
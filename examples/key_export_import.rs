@@ -14,18 +14,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Derive key
     let key = hkdf_sha512_same_len(input, salt, info);
-    println!("Derived key: {:02X?}", key);
+    println!("Derived key: {:02X?}", key.expose_secret());
 
     // Save as ASCII armor
     let prefix = "===BEGIN KEY===\n";
     let suffix = "\n===END KEY===\n";
 
-    export_key_ascii_file("mykey.asc", &key, 8, prefix, suffix)?;
+    export_key_ascii_file("mykey.asc", key.expose_secret(), 8, prefix, suffix)?;
     println!("Saved to file: mykey.asc");
 
     // Load it back
     let loaded = import_key_ascii_file("mykey.asc", prefix, suffix)?;
-    println!("Loaded key: {:02X?}", loaded);
+    println!("Loaded key: {:02X?}", loaded.expose_secret());
 
     assert_eq!(loaded, key);
     println!("Roundtrip OK.");
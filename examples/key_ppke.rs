@@ -3,10 +3,11 @@ use bitmasher::ppke::{
     export_key_password_protected_ascii_file,
     import_key_password_protected_ascii_file,
 };
+use bitmasher::secret::SecretBytes;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let plaintext_key = b"\x01\x02\x03super\xFFsecret\x00key"; // demo bytes
-    let password = b"correct horse battery staple";
+    let password = &SecretBytes::from(b"correct horse battery staple");
 
     let prefix = "===BEGIN BITMASHER KEY===\n";
     let suffix = "\n===END BITMASHER KEY===\n";
@@ -31,9 +32,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         prefix,
         suffix,
     )?;
-    println!("Imported key: {:02X?}", roundtrip);
+    println!("Imported key: {:02X?}", roundtrip.expose_secret());
 
-    assert_eq!(roundtrip, plaintext_key);
+    assert_eq!(roundtrip.expose_secret(), plaintext_key);
     println!("Roundtrip OK.");
     Ok(())
 }
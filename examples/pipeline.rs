@@ -8,7 +8,7 @@ fn main() {
 
     println!("Original: {}", result.original);
     println!("Interleaved: {:02X?}", result.interleaved);
-    println!("HKDF key: {:02X?}", result.hkdf_key);
+    println!("HKDF key: {:02X?}", result.hkdf_key.expose_secret());
     println!("Ordinals: {:?}", result.ordinals);
     println!("Final BitArray bytes: {:02X?}", result.bitarray_final.as_bytes());
 }
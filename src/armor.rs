@@ -0,0 +1,458 @@
+//! armor.rs — OpenPGP-style ASCII armor for bitmasher artifacts
+//!
+//! Layout (RFC 4880 section 6.2 style):
+//!
+//! ```text
+//! -----BEGIN BITMASHER KEY-----
+//! Version: bitmasher 0.1.0
+//! Comment: anything
+//!
+//! <base64 body, 64 characters per line>
+//! =XXXX
+//! -----END BITMASHER KEY-----
+//! ```
+//!
+//! - Header lines are `Key: Value`, terminated by one blank line (always present).
+//! - `=XXXX` is the base64 of the CRC-24 (OpenPGP polynomial) over the raw body bytes,
+//!   and is verified on decode.
+
+extern crate alloc;
+
+use alloc::{format, string::{String, ToString}, vec, vec::Vec};
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
+
+use crate::ascii_codec::{decode_bytes_ascii_with, encode_bytes_ascii_with, Encoding};
+use crate::fec::{rs_decode, rs_encode, stream_len, FecReport};
+
+/// Plain (unprotected) key, e.g. from `keygen`.
+pub const LABEL_KEY: &str = "BITMASHER KEY";
+/// PPKE password-protected key blob.
+pub const LABEL_PROTECTED_KEY: &str = "BITMASHER PROTECTED KEY";
+/// Pipeline data (e.g. v2 rotated bytes).
+pub const LABEL_DATA: &str = "BITMASHER DATA";
+/// PPKE password-protected `keyring::KeyRing`.
+pub const LABEL_KEYRING: &str = "BITMASHER KEYRING";
+/// One `shamir::Share` of a split key.
+pub const LABEL_KEY_SHARE: &str = "BITMASHER KEY SHARE";
+/// Public key (see the `Algorithm` header), e.g. a `recipient::X25519PublicKey`.
+pub const LABEL_PUBLIC_KEY: &str = "BITMASHER PUBLIC KEY";
+/// Key sealed to X25519 recipients (`recipient`).
+pub const LABEL_SEALED_KEY: &str = "BITMASHER SEALED KEY";
+/// Detached `signing` signature over another block.
+pub const LABEL_SIGNATURE: &str = "BITMASHER SIGNATURE";
+
+/// Standard header names.
+pub const HEADER_VERSION: &str = "Version";
+pub const HEADER_COMMENT: &str = "Comment";
+/// `fingerprint::KeyId` of the key a block holds, needs or was signed with.
+pub const HEADER_KEY_ID: &str = "Key-Id";
+pub const HEADER_PIPELINE: &str = "Pipeline";
+/// Public-key algorithm of a `BITMASHER PUBLIC KEY` block.
+pub const HEADER_ALGORITHM: &str = "Algorithm";
+/// Label of the block a `BITMASHER SIGNATURE` covers.
+pub const HEADER_SIGNED_LABEL: &str = "Signed-Label";
+/// `keyring::KeyRing` version id of the key an artifact was made with.
+pub const HEADER_KEY_VERSION: &str = "Key-Version";
+/// `shamir::Share` metadata: x coordinate (1..=255), shares needed, shares
+/// made, and the random id shared by all shares of one split (hex).
+pub const HEADER_SHARE_INDEX: &str = "Share-Index";
+pub const HEADER_SHARE_THRESHOLD: &str = "Share-Threshold";
+pub const HEADER_SHARE_COUNT: &str = "Share-Count";
+pub const HEADER_SHARE_SET: &str = "Share-Set";
+/// Reed–Solomon parity bytes per block (present only on FEC-protected blocks).
+pub const HEADER_FEC_PARITY: &str = "Fec-Parity";
+/// Length of the protected data in bytes (present only on FEC-protected blocks).
+pub const HEADER_FEC_LENGTH: &str = "Fec-Length";
+
+/// Base64 characters per body line.
+pub const LINE_LEN: usize = 64;
+
+/// Bytes carried by one full body line.
+const LINE_BYTES: usize = LINE_LEN / 4 * 3;
+
+const CRC24_INIT: u32 = 0x00B7_04CE;
+const CRC24_POLY: u32 = 0x0186_4CFB;
+
+/// CRC-24 as used by OpenPGP armor.
+pub fn crc24(data: &[u8]) -> u32 {
+    let mut crc = CRC24_INIT;
+    for &b in data {
+        crc ^= (b as u32) << 16;
+        for _ in 0..8 {
+            crc <<= 1;
+            if crc & 0x0100_0000 != 0 {
+                crc ^= CRC24_POLY;
+            }
+        }
+    }
+    crc & 0x00FF_FFFF
+}
+
+/// Default `Version` header value.
+pub fn version_string() -> String {
+    format!("bitmasher {}", env!("CARGO_PKG_VERSION"))
+}
+
+/// One armored block: label, ordered headers and decoded body bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Armor {
+    pub label: String,
+    pub headers: Vec<(String, String)>,
+    pub data: Vec<u8>,
+}
+
+impl Armor {
+    /// New block with a `Version` header.
+    pub fn new(label: &str, data: &[u8]) -> Self {
+        Self {
+            label: label.into(),
+            headers: vec![(HEADER_VERSION.into(), version_string())],
+            data: data.to_vec(),
+        }
+    }
+
+    /// Set header `key` (replacing an existing value), keeping insertion order.
+    pub fn with_header(mut self, key: &str, value: &str) -> Self {
+        self.set_header(key, value);
+        self
+    }
+
+    /// Set header `key` (replacing an existing value), keeping insertion order.
+    pub fn set_header(&mut self, key: &str, value: &str) {
+        match self.headers.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = value.into(),
+            None => self.headers.push((key.into(), value.into())),
+        }
+    }
+
+    /// Value of header `key`, if present.
+    pub fn header(&self, key: &str) -> Option<&str> {
+        self.headers.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+
+    /// Fail unless this block has label `label`.
+    pub fn expect_label(&self, label: &str) -> Result<(), String> {
+        if self.label != label {
+            return Err(format!("expected armor '{label}', found '{}'", self.label));
+        }
+        Ok(())
+    }
+
+    /// Render the block as text (ends with a newline after the END marker).
+    pub fn encode(&self) -> Result<String, String> {
+        self.render(&self.headers, &self.data)
+    }
+
+    /// Render the block with Reed–Solomon protection (see `fec`): the body
+    /// carries the data followed by `parity` parity bytes per block, and
+    /// `Fec-Parity`/`Fec-Length` headers are added. Decoding repairs up to
+    /// `parity` erased or `parity / 2` wrong bytes per block; a body line
+    /// that no longer decodes counts as erased, and the layout spreads one
+    /// such line thin enough to repair. Whole lines must not be dropped.
+    pub fn encode_fec(&self, parity: usize) -> Result<String, String> {
+        let stream = rs_encode(&self.data, parity)?;
+        let mut headers = self.headers.clone();
+        headers.push((HEADER_FEC_PARITY.into(), parity.to_string()));
+        headers.push((HEADER_FEC_LENGTH.into(), self.data.len().to_string()));
+        self.render(&headers, &stream)
+    }
+
+    fn render(&self, headers: &[(String, String)], body: &[u8]) -> Result<String, String> {
+        check_label(&self.label)?;
+        for (k, v) in headers {
+            check_header(k, v)?;
+        }
+
+        let mut out = String::new();
+        out.push_str(&begin_marker(&self.label));
+        out.push('\n');
+        for (k, v) in headers {
+            out.push_str(k);
+            out.push_str(": ");
+            out.push_str(v);
+            out.push('\n');
+        }
+        out.push('\n');
+        if !body.is_empty() {
+            out.push_str(&encode_bytes_ascii_with(body, LINE_LEN, Encoding::Base64));
+            out.push('\n');
+        }
+        out.push('=');
+        out.push_str(&encode_bytes_ascii_with(&crc24(&self.data).to_be_bytes()[1..], LINE_LEN, Encoding::Base64));
+        out.push('\n');
+        out.push_str(&end_marker(&self.label));
+        out.push('\n');
+        Ok(out)
+    }
+
+    /// Parse one armored block. The text must start with the BEGIN marker and
+    /// end with the matching END marker (one trailing newline is allowed).
+    /// FEC-protected blocks are repaired silently (repairs are logged).
+    pub fn decode(text: &str) -> Result<Armor, String> {
+        Self::decode_repair(text).map(|(armor, _)| armor)
+    }
+
+    /// Like [`Armor::decode`], and also report what Reed–Solomon repaired
+    /// (an empty report for blocks without FEC).
+    pub fn decode_repair(text: &str) -> Result<(Armor, FecRepair), String> {
+        let text = text.strip_suffix('\n').unwrap_or(text);
+        let mut lines = text.split('\n').enumerate().map(|(i, l)| (i + 1, l));
+
+        let (_, first) = lines.next().unwrap_or((1, ""));
+        let label = parse_begin(first).ok_or("line 1: missing BEGIN marker")?;
+        let decoded = parse_block(label, &mut lines)?;
+        if let Some((n, _)) = lines.next() {
+            return Err(format!("line {n}: unexpected text after END marker"));
+        }
+        Ok(decoded)
+    }
+
+    /// Scan `text` for every armored block, ignoring any text around them
+    /// (e.g. an email body or a log). Blocks are returned in order of appearance.
+    /// A malformed or corrupted block is an error, not skipped.
+    ///
+    /// Lenient about line endings: CRLF and trailing spaces/tabs on each line are ignored.
+    pub fn decode_all(text: &str) -> Result<Vec<Armor>, String> {
+        let mut blocks = Vec::new();
+        let mut lines = text.split('\n').enumerate().map(|(i, l)| (i + 1, l.trim_end()));
+        while let Some((n, line)) = lines.next() {
+            if let Some(label) = parse_begin(line) {
+                let (armor, _) = parse_block(label, &mut lines)
+                    .map_err(|e| format!("block {} ('{label}', line {n}): {e}", blocks.len()))?;
+                blocks.push(armor);
+            }
+        }
+        Ok(blocks)
+    }
+
+    /// First block in `text` with label `label`.
+    pub fn find(text: &str, label: &str) -> Result<Armor, String> {
+        Self::decode_all(text)?
+            .into_iter()
+            .find(|a| a.label == label)
+            .ok_or_else(|| format!("no '{label}' armor block found"))
+    }
+}
+
+/// Render several blocks one after another into a single text.
+pub fn encode_all(blocks: &[Armor]) -> Result<String, String> {
+    let mut out = String::new();
+    for b in blocks {
+        out.push_str(&b.encode()?);
+    }
+    Ok(out)
+}
+
+/// What Reed–Solomon repaired in an FEC-protected block.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FecRepair {
+    /// Byte-level report over the FEC stream (data followed by parity).
+    pub report: FecReport,
+    /// Text lines (1-based) that had at least one repaired byte.
+    pub lines: Vec<usize>,
+}
+
+/// Parse the rest of a block whose BEGIN line (with `label`) was already consumed,
+/// up to and including its END marker. `lines` yields (1-based line number, line).
+fn parse_block<'a>(
+    label: &str,
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+) -> Result<(Armor, FecRepair), String> {
+    let mut next_line =
+        |what: &str| lines.next().ok_or_else(|| format!("{what} (input ended)"));
+
+    let mut headers = Vec::new();
+    let body_line = loop {
+        let (n, line) = next_line("missing blank line after headers")?;
+        if line.is_empty() {
+            break n + 1;
+        }
+        // "Key: value"; an empty value is written as "Key: " and arrives trimmed
+        let (k, v) = line
+            .split_once(':')
+            .ok_or_else(|| format!("line {n}: malformed header line '{line}'"))?;
+        let v = v.strip_prefix(' ').unwrap_or(v);
+        headers.push((k.to_string(), v.to_string()));
+    };
+
+    let mut body_lines = Vec::new();
+    let (crc_at, crc_line) = loop {
+        let (n, line) = next_line("missing checksum line")?;
+        if is_checksum_line(line) {
+            break (n, line);
+        }
+        if line.starts_with("-----") {
+            return Err(format!("line {n}: missing checksum line"));
+        }
+        body_lines.push(line);
+    };
+
+    let (n, end) = next_line("missing END marker")?;
+    if end != end_marker(label) {
+        return Err(format!("line {n}: expected '{}', found '{end}'", end_marker(label)));
+    }
+
+    let (data, repair) = if headers.iter().any(|(k, _)| k == HEADER_FEC_PARITY) {
+        decode_body_fec(&mut headers, &body_lines, body_line)?
+    } else {
+        let data = decode_bytes_ascii_with(&body_lines.concat(), Encoding::Base64)
+            .map_err(|e| format!("lines {body_line}-{}: body: {e}", crc_at - 1))?;
+        (data, FecRepair::default())
+    };
+    let crc = decode_bytes_ascii_with(&crc_line[1..], Encoding::Base64)
+        .map_err(|e| format!("line {crc_at}: checksum: {e}"))?;
+    let expected = u32::from_be_bytes([0, crc[0], crc[1], crc[2]]);
+    let actual = crc24(&data);
+    if expected != actual {
+        return Err(format!(
+            "line {crc_at}: CRC-24 mismatch: armor says {expected:06X}, body is {actual:06X}"
+        ));
+    }
+
+    if !repair.report.is_clean() {
+        log::warn!(
+            "armor '{label}': Reed-Solomon repaired {} byte(s) on line(s) {:?}",
+            repair.report.corrected.len() + repair.report.erased.len(),
+            repair.lines
+        );
+    }
+    Ok((Armor { label: label.to_string(), headers, data }, repair))
+}
+
+/// Decode an FEC-protected body line by line. A line that does not decode to
+/// its expected length marks all of its bytes as erasures; Reed–Solomon then
+/// repairs erasures and wrong bytes. The `Fec-*` headers are removed.
+fn decode_body_fec(
+    headers: &mut Vec<(String, String)>,
+    body_lines: &[&str],
+    first_line: usize,
+) -> Result<(Vec<u8>, FecRepair), String> {
+    let take = |headers: &mut Vec<(String, String)>, key: &str| -> Result<usize, String> {
+        let i = headers
+            .iter()
+            .position(|(k, _)| k == key)
+            .ok_or_else(|| format!("missing {key} header"))?;
+        let (_, v) = headers.remove(i);
+        v.trim().parse().map_err(|_| format!("invalid {key} header '{v}'"))
+    };
+    let parity = take(headers, HEADER_FEC_PARITY)?;
+    let data_len = take(headers, HEADER_FEC_LENGTH)?;
+
+    let total = stream_len(data_len, parity)?;
+    let expected_lines = total.div_ceil(LINE_BYTES);
+    if body_lines.len() != expected_lines {
+        return Err(format!(
+            "FEC body has {} lines, expected {expected_lines} (lines cannot be added or dropped)",
+            body_lines.len()
+        ));
+    }
+
+    let mut stream = vec![0u8; total];
+    let mut erasures = Vec::new();
+    for (i, line) in body_lines.iter().enumerate() {
+        let start = i * LINE_BYTES;
+        let end = (start + LINE_BYTES).min(total);
+        match decode_bytes_ascii_with(line, Encoding::Base64) {
+            Ok(bytes) if bytes.len() == end - start => stream[start..end].copy_from_slice(&bytes),
+            _ => erasures.extend(start..end),
+        }
+    }
+
+    let (data, report) = rs_decode(&stream, data_len, parity, &erasures)
+        .map_err(|e| format!("FEC: {e}"))?;
+    let mut lines: Vec<usize> = report
+        .corrected
+        .iter()
+        .chain(&report.erased)
+        .map(|&p| first_line + p / LINE_BYTES)
+        .collect();
+    lines.sort_unstable();
+    lines.dedup();
+    Ok((data, FecRepair { report, lines }))
+}
+
+fn begin_marker(label: &str) -> String {
+    format!("-----BEGIN {label}-----")
+}
+
+fn end_marker(label: &str) -> String {
+    format!("-----END {label}-----")
+}
+
+/// Label from a `-----BEGIN <label>-----` line.
+fn parse_begin(line: &str) -> Option<&str> {
+    let label = line.strip_prefix("-----BEGIN ")?.strip_suffix("-----")?;
+    (!label.is_empty()).then_some(label)
+}
+
+/// `=XXXX`: exactly 4 base64 characters, which a body line can never look like.
+fn is_checksum_line(line: &str) -> bool {
+    line.len() == 5 && line.starts_with('=') && !line[1..].contains('=')
+}
+
+fn check_label(label: &str) -> Result<(), String> {
+    if label.is_empty() || label.contains(['\n', '\r']) || label.contains("-----") {
+        return Err(format!("invalid armor label '{label}'"));
+    }
+    Ok(())
+}
+
+fn check_header(key: &str, value: &str) -> Result<(), String> {
+    if key.is_empty() || key.contains([':', '\n', '\r', ' ']) {
+        return Err(format!("invalid header name '{key}'"));
+    }
+    // lines are trimmed on decode, so surrounding whitespace would not round-trip
+    if value.contains(['\n', '\r']) || value.trim() != value {
+        return Err(format!("invalid value for header '{key}'"));
+    }
+    Ok(())
+}
+
+// --------------------- File helpers (std only) ---------------------
+
+#[cfg(feature = "std")]
+pub fn write_armor_file(path: &str, armor: &Armor) -> Result<(), Box<dyn std::error::Error>> {
+    use std::fs;
+    fs::write(path, armor.encode()?)?;
+    Ok(())
+}
+
+/// Write one block with Reed–Solomon protection (see [`Armor::encode_fec`]).
+#[cfg(feature = "std")]
+pub fn write_armor_file_fec(
+    path: &str,
+    armor: &Armor,
+    parity: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use std::fs;
+    fs::write(path, armor.encode_fec(parity)?)?;
+    Ok(())
+}
+
+/// Write several blocks into one file (e.g. a wrapped key and its data).
+#[cfg(feature = "std")]
+pub fn write_armor_file_all(path: &str, blocks: &[Armor]) -> Result<(), Box<dyn std::error::Error>> {
+    use std::fs;
+    fs::write(path, encode_all(blocks)?)?;
+    Ok(())
+}
+
+/// Read the first block labeled `label` from a file.
+/// The file may hold other blocks and surrounding text.
+#[cfg(feature = "std")]
+pub fn read_armor_file(path: &str, label: &str) -> Result<Armor, Box<dyn std::error::Error>> {
+    use std::fs;
+    let text = fs::read_to_string(path)?;
+    let armor = Armor::find(&text, label).map_err(|e| format!("decode armor: {e}"))?;
+    Ok(armor)
+}
+
+/// Read every block from a file, in order.
+#[cfg(feature = "std")]
+pub fn read_armor_file_all(path: &str) -> Result<Vec<Armor>, Box<dyn std::error::Error>> {
+    use std::fs;
+    let text = fs::read_to_string(path)?;
+    let blocks = Armor::decode_all(&text).map_err(|e| format!("decode armor: {e}"))?;
+    Ok(blocks)
+}
@@ -1,6 +1,10 @@
 extern crate alloc;
 
 use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use alloc::{boxed::Box, format};
+
+use crate::wordlist::{PGP_EVEN_WORDS, PGP_ODD_WORDS};
 
 /// Encode a slice of u8 bytes into an ASCII string.
 /// Each byte becomes a 3‑digit decimal value (000–255).
@@ -12,23 +16,9 @@ pub fn encode_bytes_ascii(input: &[u8], per_line: usize) -> String {
     let mut out = String::new();
     for (i, &b) in input.iter().enumerate() {
         // 3-digit decimal, zero-padded
-        match b {
-            0..=9   => out.push_str("00"),
-            10..=99 => out.push('0'),
-            _       => {}
-        }
-        // write the decimal digits
-        // (avoid format! to be friendlier to no_std + alloc if you later want)
-        // but format! is fine if you always compile tests with std:
-        // out.push_str(&b.to_string());
-        // Manual fast push since above prepadded the width:
-        // Convert u8 to decimal without allocation:
-        let val = b as u8;
-        // We already emitted the required leading zeros; now push the actual number.
-        // For simplicity and clarity, use to_string() here (requires std). If you want
-        // a pure alloc approach, keep format!/to_string() gated under std and add
-        // a small decimal writer. For most crates, to_string() in tests is fine:
-        out.push_str(&val.to_string());
+        for d in [b / 100, b / 10 % 10, b % 10] {
+            out.push(char::from(b'0' + d));
+        }
 
         // Determine separators
         let is_last = i + 1 == input.len();
@@ -49,7 +39,7 @@ pub fn encode_bytes_ascii(input: &[u8], per_line: usize) -> String {
 /// Each token must be exactly 3 digits 000–255.
 pub fn decode_bytes_ascii(input: &str) -> Result<Vec<u8>, &'static str> {
     let mut out = Vec::new();
-    for token in input.split(|c| c == ' ' || c == '\n').filter(|t| !t.is_empty()) {
+    for token in input.split([' ', '\n']).filter(|t| !t.is_empty()) {
         if token.len() != 3 {
             return Err("token is not 3 characters");
         }
@@ -95,12 +85,663 @@ pub fn decode_bytes_ascii_wrapped(
     decode_bytes_ascii(inner)
 }
 
+// --------------------- Lenient decoding ---------------------
+
+/// What went wrong in a lenient decode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeErrorKind {
+    MissingPrefix,
+    MissingSuffix,
+    TokenLength,
+    InvalidNumber,
+    OutOfRange,
+    UnknownWord,
+    WordParity,
+    InvalidHex,
+    LineNumber,
+    LineChecksum,
+    Checksum,
+    InvalidChar,
+    Padding,
+}
+
+impl DecodeErrorKind {
+    pub fn message(&self) -> &'static str {
+        match self {
+            DecodeErrorKind::MissingPrefix => "missing prefix",
+            DecodeErrorKind::MissingSuffix => "missing suffix",
+            DecodeErrorKind::TokenLength => "token is not 3 characters",
+            DecodeErrorKind::InvalidNumber => "invalid decimal number",
+            DecodeErrorKind::OutOfRange => "value out of range 0–255",
+            DecodeErrorKind::UnknownWord => "unknown word",
+            DecodeErrorKind::WordParity => "word from the wrong (even/odd) list: swapped or missing word",
+            DecodeErrorKind::InvalidHex => "invalid hex digits",
+            DecodeErrorKind::LineNumber => "line number out of sequence",
+            DecodeErrorKind::LineChecksum => "line checksum mismatch: this line is mistyped",
+            DecodeErrorKind::Checksum => "total length/checksum mismatch",
+            DecodeErrorKind::InvalidChar => "character not in alphabet",
+            DecodeErrorKind::Padding => "invalid final symbol: bad padding or misplaced tail symbol",
+        }
+    }
+}
+
+/// Decode error with its location in the input text.
+/// `line` and `column` are 1-based (column counts characters),
+/// `token` is the 0-based index of the offending token in the body.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodeError {
+    pub kind: DecodeErrorKind,
+    pub line: usize,
+    pub column: usize,
+    pub token: usize,
+}
+
+impl DecodeError {
+    pub(crate) fn at(kind: DecodeErrorKind, text: &str, byte_offset: usize, token: usize) -> Self {
+        let before = &text[..byte_offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = before[line_start..].chars().count() + 1;
+        Self { kind, line, column, token }
+    }
+}
+
+impl core::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "line {}, column {} (token {}): {}",
+            self.line,
+            self.column,
+            self.token,
+            self.kind.message()
+        )
+    }
+}
+
+impl core::error::Error for DecodeError {}
+
+/// Decode the tokens of `text[start..end]`, splitting on any whitespace
+/// (spaces, tabs, CR, LF, ...). Error positions refer to `text`.
+fn decode_decimal_tokens(text: &str, start: usize, end: usize) -> Result<Vec<u8>, DecodeError> {
+    let mut out = Vec::new();
+    let body = &text[start..end];
+    let mut token_start: Option<usize> = None;
+    // trailing sentinel whitespace flushes the last token
+    for (i, c) in body.char_indices().chain(core::iter::once((body.len(), ' '))) {
+        match (c.is_whitespace(), token_start) {
+            (false, None) => token_start = Some(i),
+            (true, Some(ts)) => {
+                let token = &body[ts..i];
+                let err = |kind| DecodeError::at(kind, text, start + ts, out.len());
+                if token.chars().count() != 3 {
+                    return Err(err(DecodeErrorKind::TokenLength));
+                }
+                if !token.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(err(DecodeErrorKind::InvalidNumber));
+                }
+                let val: u32 = token.parse().map_err(|_| err(DecodeErrorKind::InvalidNumber))?;
+                if val > 255 {
+                    return Err(err(DecodeErrorKind::OutOfRange));
+                }
+                out.push(val as u8);
+                token_start = None;
+            }
+            _ => {}
+        }
+    }
+    Ok(out)
+}
+
+/// Lenient variant of [`decode_bytes_ascii`]: any whitespace separates tokens
+/// (tabs, CRLF, repeated blanks), and errors carry line/column/token.
+pub fn decode_bytes_ascii_lenient(input: &str) -> Result<Vec<u8>, DecodeError> {
+    decode_decimal_tokens(input, 0, input.len())
+}
+
+/// Lenient variant of [`decode_bytes_ascii_wrapped`].
+///
+/// - CRLF vs LF and other whitespace differences are ignored, also inside
+///   `prefix` and `suffix` (they are compared with surrounding whitespace trimmed);
+/// - leading/trailing blank lines around the block are tolerated;
+/// - errors carry line/column/token positions in `text`.
+pub fn decode_bytes_ascii_wrapped_lenient(
+    text: &str,
+    prefix: &str,
+    suffix: &str,
+) -> Result<Vec<u8>, DecodeError> {
+    let prefix = prefix.trim();
+    let suffix = suffix.trim();
+
+    let start = text.len() - text.trim_start().len();
+    let end = text.trim_end().len();
+    let trimmed = &text[start..end];
+
+    if !starts_with_ws_insensitive(trimmed, prefix) {
+        return Err(DecodeError::at(DecodeErrorKind::MissingPrefix, text, start, 0));
+    }
+    let body_start = start + prefix_len_ws_insensitive(trimmed, prefix);
+    let Some(suffix_len) = suffix_len_ws_insensitive(&text[body_start..end], suffix) else {
+        let tokens = decode_decimal_tokens(text, body_start, end).map(|v| v.len()).unwrap_or(0);
+        return Err(DecodeError::at(DecodeErrorKind::MissingSuffix, text, end, tokens));
+    };
+
+    decode_decimal_tokens(text, body_start, end - suffix_len)
+}
+
+/// Non-whitespace characters of `s`, in order.
+fn non_ws(s: &str) -> impl DoubleEndedIterator<Item = (usize, char)> + '_ {
+    s.char_indices().filter(|(_, c)| !c.is_whitespace())
+}
+
+fn starts_with_ws_insensitive(text: &str, prefix: &str) -> bool {
+    let mut t = non_ws(text).map(|(_, c)| c);
+    non_ws(prefix).all(|(_, p)| t.next() == Some(p))
+}
+
+/// Byte length of the part of `text` that matches `prefix` (ignoring whitespace).
+fn prefix_len_ws_insensitive(text: &str, prefix: &str) -> usize {
+    let n = non_ws(prefix).count();
+    if n == 0 {
+        return 0;
+    }
+    non_ws(text).nth(n - 1).map_or(0, |(i, c)| i + c.len_utf8())
+}
+
+/// Byte length of the tail of `text` that matches `suffix` (ignoring whitespace).
+fn suffix_len_ws_insensitive(text: &str, suffix: &str) -> Option<usize> {
+    let mut t = non_ws(text).rev();
+    let mut first = text.len();
+    for (_, p) in non_ws(suffix).rev() {
+        let (i, c) = t.next()?;
+        if c != p {
+            return None;
+        }
+        first = i;
+    }
+    Some(text.len() - first)
+}
+
+// --------------------- PGP word list ---------------------
+
+/// Encode bytes as PGP words: even positions from the even list, odd
+/// positions from the odd list. `per_line` words per line, separated by
+/// spaces; lines separated by '\n' (no trailing space or newline).
+pub fn encode_pgp_words(input: &[u8], per_line: usize) -> String {
+    assert!(per_line >= 1, "per_line must be >= 1");
+
+    let mut out = String::new();
+    for (i, &b) in input.iter().enumerate() {
+        if i > 0 {
+            out.push(if i.is_multiple_of(per_line) { '\n' } else { ' ' });
+        }
+        let list = if i.is_multiple_of(2) { &PGP_EVEN_WORDS } else { &PGP_ODD_WORDS };
+        out.push_str(list[b as usize]);
+    }
+    out
+}
+
+/// Decode PGP words back to bytes.
+/// Case-insensitive; any whitespace separates words. A word from the wrong
+/// list for its position (swap, drop or duplicate) is reported with its location.
+pub fn decode_pgp_words(input: &str) -> Result<Vec<u8>, DecodeError> {
+    let mut out = Vec::new();
+    for word in input.split_whitespace() {
+        let at = word.as_ptr() as usize - input.as_ptr() as usize;
+        let find = |list: &[&str; 256]| list.iter().position(|w| w.eq_ignore_ascii_case(word));
+        let (own, other) = if out.len().is_multiple_of(2) {
+            (&PGP_EVEN_WORDS, &PGP_ODD_WORDS)
+        } else {
+            (&PGP_ODD_WORDS, &PGP_EVEN_WORDS)
+        };
+        match find(own) {
+            Some(v) => out.push(v as u8),
+            None => {
+                let kind = if find(other).is_some() {
+                    DecodeErrorKind::WordParity
+                } else {
+                    DecodeErrorKind::UnknownWord
+                };
+                return Err(DecodeError::at(kind, input, at, out.len()));
+            }
+        }
+    }
+    Ok(out)
+}
+
+// --------------------- Arbitrary radix ---------------------
+
+/// Bitcoin Base58 alphabet (no 0, O, I, l).
+pub const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+/// Base36: digits then lowercase letters.
+pub const BASE36_ALPHABET: &str = "0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Alphabet for [`encode_radix`]/[`decode_radix`]: 2 to 65536 distinct,
+/// non-whitespace characters (any Unicode). The first character is the zero digit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadixAlphabet {
+    chars: Vec<char>,
+}
+
+impl RadixAlphabet {
+    pub fn new(alphabet: &str) -> Result<Self, &'static str> {
+        let chars: Vec<char> = alphabet.chars().collect();
+        if !(2..=65536).contains(&chars.len()) {
+            return Err("alphabet must have 2 to 65536 characters");
+        }
+        if chars.iter().any(|c| c.is_whitespace()) {
+            return Err("alphabet must not contain whitespace");
+        }
+        for (i, c) in chars.iter().enumerate() {
+            if chars[..i].contains(c) {
+                return Err("alphabet characters must be distinct");
+            }
+        }
+        Ok(Self { chars })
+    }
+
+    pub fn base58() -> Self {
+        Self::new(BASE58_ALPHABET).expect("valid alphabet")
+    }
+
+    pub fn base36() -> Self {
+        Self::new(BASE36_ALPHABET).expect("valid alphabet")
+    }
+
+    /// Number of digits (the base).
+    pub fn radix(&self) -> usize {
+        self.chars.len()
+    }
+
+    fn digit(&self, c: char) -> Option<u32> {
+        self.chars.iter().position(|&a| a == c).map(|p| p as u32)
+    }
+}
+
+/// Encode bytes as one big-endian number in the alphabet's base.
+/// Each leading zero byte becomes one leading zero digit (as in Base58),
+/// so leading zeros survive the round trip. No line breaks are inserted.
+pub fn encode_radix(input: &[u8], alphabet: &RadixAlphabet) -> String {
+    let radix = alphabet.radix() as u32;
+    let zeros = input.iter().take_while(|&&b| b == 0).count();
+
+    // little-endian digits of the number formed by the remaining bytes
+    let mut digits: Vec<u32> = Vec::with_capacity(input.len() * 2);
+    for &b in &input[zeros..] {
+        let mut carry = b as u32;
+        for d in digits.iter_mut() {
+            carry += *d << 8;
+            *d = carry % radix;
+            carry /= radix;
+        }
+        while carry > 0 {
+            digits.push(carry % radix);
+            carry /= radix;
+        }
+    }
+
+    let zero = alphabet.chars[0];
+    core::iter::repeat_n(zero, zeros)
+        .chain(digits.iter().rev().map(|&d| alphabet.chars[d as usize]))
+        .collect()
+}
+
+/// Decode text produced by [`encode_radix`] with the same alphabet.
+/// Whitespace is ignored; an unknown character is reported with its location.
+pub fn decode_radix(input: &str, alphabet: &RadixAlphabet) -> Result<Vec<u8>, DecodeError> {
+    let radix = alphabet.radix() as u32;
+    let mut zeros = 0;
+    let mut leading = true;
+    // little-endian bytes of the number
+    let mut bytes: Vec<u8> = Vec::new();
+    for (i, (at, c)) in non_ws(input).enumerate() {
+        let d = alphabet
+            .digit(c)
+            .ok_or_else(|| DecodeError::at(DecodeErrorKind::InvalidChar, input, at, i))?;
+        if leading && d == 0 {
+            zeros += 1;
+            continue;
+        }
+        leading = false;
+        let mut carry = d;
+        for b in bytes.iter_mut() {
+            carry += *b as u32 * radix;
+            *b = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    bytes.extend(core::iter::repeat_n(0, zeros));
+    bytes.reverse();
+    Ok(bytes)
+}
+
+// --------------------- Pluggable encodings ---------------------
+
+/// Binary-to-text encodings supported by the `*_with` functions.
+///
+/// `Decimal` is the original 3-digit format, where `per_line` counts bytes.
+/// For every other encoding `per_line` counts output characters per line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    /// 3-digit decimal per byte (000–255), space separated.
+    Decimal,
+    /// RFC 4648 base16, uppercase (decoding is case-insensitive).
+    Hex,
+    /// RFC 4648 base32 with '=' padding.
+    Base32,
+    /// RFC 4648 base64 with '=' padding.
+    Base64,
+    /// RFC 4648 base64url, unpadded (padding accepted on decode).
+    Base64Url,
+    /// Adobe/btoa Ascii85 without `<~ ~>` delimiters; 'z' for a zero group.
+    Ascii85,
+    /// ZeroMQ Z85. Inputs that are not a multiple of 4 bytes use the
+    /// Ascii85 partial-group rule (n bytes -> n+1 characters).
+    Z85,
+    /// PGP word list, one word per byte; `per_line` counts words.
+    PgpWords,
+    /// Bitcoin Base58 (see [`encode_radix`]).
+    Base58,
+    /// Base36, lowercase (decoding is case-insensitive).
+    Base36,
+}
+
+impl Encoding {
+    /// Stable lowercase name, suitable for headers and CLI flags.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Decimal => "decimal",
+            Encoding::Hex => "hex",
+            Encoding::Base32 => "base32",
+            Encoding::Base64 => "base64",
+            Encoding::Base64Url => "base64url",
+            Encoding::Ascii85 => "ascii85",
+            Encoding::Z85 => "z85",
+            Encoding::PgpWords => "pgp-words",
+            Encoding::Base58 => "base58",
+            Encoding::Base36 => "base36",
+        }
+    }
+
+    /// Inverse of [`Encoding::name`] (case-insensitive).
+    pub fn from_name(name: &str) -> Option<Encoding> {
+        [
+            Encoding::Decimal,
+            Encoding::Hex,
+            Encoding::Base32,
+            Encoding::Base64,
+            Encoding::Base64Url,
+            Encoding::Ascii85,
+            Encoding::Z85,
+            Encoding::PgpWords,
+            Encoding::Base58,
+            Encoding::Base36,
+        ]
+        .into_iter()
+        .find(|e| e.name().eq_ignore_ascii_case(name))
+    }
+}
+
+const HEX_ALPHABET: &[u8; 16] = b"0123456789ABCDEF";
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64URL_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const Z85_ALPHABET: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+/// Encode with a power-of-two alphabet (`bits` = 4, 5 or 6), MSB first,
+/// then pad with '=' to a multiple of `pad_to` characters (0 = no padding).
+fn encode_radix2(input: &[u8], alphabet: &[u8], bits: u32, pad_to: usize) -> String {
+    let mut out = String::with_capacity((input.len() * 8).div_ceil(bits as usize) + pad_to);
+    let mask = (1u32 << bits) - 1;
+    let mut acc: u32 = 0;
+    let mut acc_bits: u32 = 0;
+    for &b in input {
+        acc = (acc << 8) | b as u32;
+        acc_bits += 8;
+        while acc_bits >= bits {
+            acc_bits -= bits;
+            out.push(alphabet[((acc >> acc_bits) & mask) as usize] as char);
+        }
+        acc &= (1 << acc_bits) - 1;
+    }
+    if acc_bits > 0 {
+        out.push(alphabet[((acc << (bits - acc_bits)) & mask) as usize] as char);
+    }
+    if pad_to > 0 {
+        while !out.len().is_multiple_of(pad_to) {
+            out.push('=');
+        }
+    }
+    out
+}
+
+/// Inverse of `encode_radix2`. `input` must not contain whitespace.
+/// Trailing '=' padding is optional; leftover bits must be zero.
+fn decode_radix2(
+    input: &str,
+    alphabet: &[u8],
+    bits: u32,
+    case_insensitive: bool,
+) -> Result<Vec<u8>, &'static str> {
+    let body = input.trim_end_matches('=');
+    let mut out = Vec::with_capacity(body.len() * bits as usize / 8);
+    let mut acc: u32 = 0;
+    let mut acc_bits: u32 = 0;
+    for c in body.bytes() {
+        let c = if case_insensitive { c.to_ascii_uppercase() } else { c };
+        let v = alphabet.iter().position(|&a| a == c).ok_or("invalid character")? as u32;
+        acc = (acc << bits) | v;
+        acc_bits += bits;
+        if acc_bits >= 8 {
+            acc_bits -= 8;
+            out.push((acc >> acc_bits) as u8);
+            acc &= (1 << acc_bits) - 1;
+        }
+    }
+    if acc_bits >= bits {
+        return Err("invalid encoded length");
+    }
+    if acc != 0 {
+        return Err("non-zero trailing bits");
+    }
+    Ok(out)
+}
+
+/// Base85 encode in 4-byte groups; a final group of n bytes yields n+1 characters.
+/// `zero_group` is emitted for a full all-zero group if given (Ascii85 'z').
+fn encode_base85(input: &[u8], alphabet: &dyn Fn(u32) -> u8, zero_group: Option<char>) -> String {
+    let mut out = String::with_capacity(input.len().div_ceil(4) * 5);
+    for chunk in input.chunks(4) {
+        let mut group = [0u8; 4];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let mut v = u32::from_be_bytes(group);
+        if let Some(z) = zero_group
+            && chunk.len() == 4
+            && v == 0
+        {
+            out.push(z);
+            continue;
+        }
+        let mut digits = [0u8; 5];
+        for d in digits.iter_mut().rev() {
+            *d = alphabet(v % 85);
+            v /= 85;
+        }
+        for &d in &digits[..chunk.len() + 1] {
+            out.push(d as char);
+        }
+    }
+    out
+}
+
+/// Inverse of `encode_base85`. `input` must not contain whitespace.
+fn decode_base85(
+    input: &str,
+    value_of: &dyn Fn(u8) -> Option<u32>,
+    zero_group: Option<u8>,
+) -> Result<Vec<u8>, &'static str> {
+    let mut out = Vec::with_capacity(input.len() / 5 * 4 + 4);
+    let mut digits: Vec<u32> = Vec::with_capacity(5);
+    let flush = |digits: &mut Vec<u32>, out: &mut Vec<u8>| -> Result<(), &'static str> {
+        let n = digits.len();
+        if n == 1 {
+            return Err("invalid encoded length");
+        }
+        // pad a partial group with the highest digit, then drop the extra bytes
+        digits.resize(5, 84);
+        let mut v: u64 = 0;
+        for &d in digits.iter() {
+            v = v * 85 + d as u64;
+        }
+        if v > u32::MAX as u64 {
+            return Err("base85 group out of range");
+        }
+        out.extend_from_slice(&(v as u32).to_be_bytes()[..n - 1]);
+        digits.clear();
+        Ok(())
+    };
+    for c in input.bytes() {
+        if Some(c) == zero_group {
+            if !digits.is_empty() {
+                return Err("'z' inside a group");
+            }
+            out.extend_from_slice(&[0, 0, 0, 0]);
+            continue;
+        }
+        digits.push(value_of(c).ok_or("invalid character")?);
+        if digits.len() == 5 {
+            flush(&mut digits, &mut out)?;
+        }
+    }
+    if !digits.is_empty() {
+        flush(&mut digits, &mut out)?;
+    }
+    Ok(out)
+}
+
+/// Break `encoded` into lines of `per_line` characters, joined by '\n' (no trailing newline).
+fn wrap_lines(encoded: &str, per_line: usize) -> String {
+    let mut out = String::with_capacity(encoded.len() + encoded.len() / per_line);
+    for (i, c) in encoded.chars().enumerate() {
+        if i > 0 && i % per_line == 0 {
+            out.push('\n');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Encode bytes with the chosen `encoding`.
+/// `Decimal` behaves exactly like [`encode_bytes_ascii`]; other encodings
+/// are wrapped at `per_line` characters per line.
+pub fn encode_bytes_ascii_with(input: &[u8], per_line: usize, encoding: Encoding) -> String {
+    assert!(per_line >= 1, "per_line must be >= 1");
+
+    let encoded = match encoding {
+        Encoding::Decimal => return encode_bytes_ascii(input, per_line),
+        Encoding::PgpWords => return encode_pgp_words(input, per_line),
+        Encoding::Hex => encode_radix2(input, HEX_ALPHABET, 4, 0),
+        Encoding::Base32 => encode_radix2(input, BASE32_ALPHABET, 5, 8),
+        Encoding::Base64 => encode_radix2(input, BASE64_ALPHABET, 6, 4),
+        Encoding::Base64Url => encode_radix2(input, BASE64URL_ALPHABET, 6, 0),
+        Encoding::Ascii85 => encode_base85(input, &|d| b'!' + d as u8, Some('z')),
+        Encoding::Z85 => encode_base85(input, &|d| Z85_ALPHABET[d as usize], None),
+        Encoding::Base58 => encode_radix(input, &RadixAlphabet::base58()),
+        Encoding::Base36 => encode_radix(input, &RadixAlphabet::base36()),
+    };
+    wrap_lines(&encoded, per_line)
+}
+
+/// Decode text produced by [`encode_bytes_ascii_with`] using the same `encoding`.
+/// Spaces, tabs, '\r' and '\n' between characters are ignored.
+pub fn decode_bytes_ascii_with(input: &str, encoding: Encoding) -> Result<Vec<u8>, &'static str> {
+    match encoding {
+        // `decode_bytes_ascii` splits on ' ' and '\n' only
+        Encoding::Decimal => return decode_bytes_ascii(&input.replace(['\t', '\r'], " ")),
+        Encoding::PgpWords => return decode_pgp_words(input).map_err(|e| e.kind.message()),
+        Encoding::Base58 => {
+            return decode_radix(input, &RadixAlphabet::base58()).map_err(|e| e.kind.message());
+        }
+        Encoding::Base36 => {
+            return decode_radix(&input.to_ascii_lowercase(), &RadixAlphabet::base36())
+                .map_err(|e| e.kind.message());
+        }
+        _ => {}
+    }
+    let compact: String = input
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '\t' | '\r' | '\n'))
+        .collect();
+    match encoding {
+        Encoding::Decimal | Encoding::PgpWords | Encoding::Base58 | Encoding::Base36 => {
+            unreachable!()
+        }
+        Encoding::Hex => decode_radix2(&compact, HEX_ALPHABET, 4, true),
+        Encoding::Base32 => decode_radix2(&compact, BASE32_ALPHABET, 5, true),
+        Encoding::Base64 => decode_radix2(&compact, BASE64_ALPHABET, 6, false),
+        Encoding::Base64Url => decode_radix2(&compact, BASE64URL_ALPHABET, 6, false),
+        Encoding::Ascii85 => decode_base85(
+            &compact,
+            &|c| (b'!'..=b'u').contains(&c).then(|| (c - b'!') as u32),
+            Some(b'z'),
+        ),
+        Encoding::Z85 => decode_base85(
+            &compact,
+            &|c| Z85_ALPHABET.iter().position(|&a| a == c).map(|p| p as u32),
+            None,
+        ),
+    }
+}
+
+/// Encode bytes with `encoding` and wrap with prefix + suffix.
+pub fn encode_bytes_ascii_wrapped_with(
+    data: &[u8],
+    per_line: usize,
+    prefix: &str,
+    suffix: &str,
+    encoding: Encoding,
+) -> String {
+    let body = encode_bytes_ascii_with(data, per_line, encoding);
+    let mut s = String::with_capacity(prefix.len() + body.len() + suffix.len());
+    s.push_str(prefix);
+    s.push_str(&body);
+    s.push_str(suffix);
+    s
+}
+
+/// Strip prefix/suffix and decode the body with `encoding`.
+pub fn decode_bytes_ascii_wrapped_with(
+    text: &str,
+    prefix: &str,
+    suffix: &str,
+    encoding: Encoding,
+) -> Result<Vec<u8>, &'static str> {
+    if !text.starts_with(prefix) {
+        return Err("missing prefix");
+    }
+    if !text.ends_with(suffix) {
+        return Err("missing suffix");
+    }
+
+    let inner = &text[prefix.len() .. text.len() - suffix.len()];
+    decode_bytes_ascii_with(inner, encoding)
+}
+
+#[cfg(feature = "std")]
 pub fn read_ascii_file(path: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
     use std::fs;
     let content = fs::read_to_string(path)?;
     decode_bytes_ascii(&content).map_err(|e| e.into())
 }
 
+#[cfg(feature = "std")]
 pub fn read_ascii_file_wrapped(
     path: &str,
     prefix: &str,
@@ -113,6 +754,7 @@ pub fn read_ascii_file_wrapped(
     Ok(decoded)
 }
 
+#[cfg(feature = "std")]
 pub fn write_ascii_file(
     path: &str,
     data: &[u8],
@@ -123,6 +765,7 @@ pub fn write_ascii_file(
     fs::write(path, encoded)
 }
 
+#[cfg(feature = "std")]
 pub fn write_ascii_file_wrapped(
     path: &str,
     data: &[u8],
@@ -134,3 +777,266 @@ pub fn write_ascii_file_wrapped(
     let encoded = encode_bytes_ascii_wrapped(data, per_line, prefix, suffix);
     fs::write(path, encoded)
 }
+
+#[cfg(feature = "std")]
+pub fn read_ascii_file_with(
+    path: &str,
+    encoding: Encoding,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    use std::fs;
+    let content = fs::read_to_string(path)?;
+    decode_bytes_ascii_with(&content, encoding).map_err(|e| e.into())
+}
+
+#[cfg(feature = "std")]
+pub fn read_ascii_file_wrapped_with(
+    path: &str,
+    prefix: &str,
+    suffix: &str,
+    encoding: Encoding,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    use std::fs;
+    let content = fs::read_to_string(path)?;
+    let decoded = decode_bytes_ascii_wrapped_with(&content, prefix, suffix, encoding)
+        .map_err(|e| format!("decode error: {}", e))?;
+    Ok(decoded)
+}
+
+#[cfg(feature = "std")]
+pub fn write_ascii_file_with(
+    path: &str,
+    data: &[u8],
+    per_line: usize,
+    encoding: Encoding,
+) -> std::io::Result<()> {
+    use std::fs;
+    let encoded = encode_bytes_ascii_with(data, per_line, encoding);
+    fs::write(path, encoded)
+}
+
+#[cfg(feature = "std")]
+pub fn write_ascii_file_wrapped_with(
+    path: &str,
+    data: &[u8],
+    per_line: usize,
+    prefix: &str,
+    suffix: &str,
+    encoding: Encoding,
+) -> std::io::Result<()> {
+    use std::fs;
+    let encoded = encode_bytes_ascii_wrapped_with(data, per_line, prefix, suffix, encoding);
+    fs::write(path, encoded)
+}
+
+/// Lenient variant of [`read_ascii_file_wrapped`] (see [`decode_bytes_ascii_wrapped_lenient`]).
+#[cfg(feature = "std")]
+pub fn read_ascii_file_wrapped_lenient(
+    path: &str,
+    prefix: &str,
+    suffix: &str,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    use std::fs;
+    let content = fs::read_to_string(path)?;
+    let decoded = decode_bytes_ascii_wrapped_lenient(&content, prefix, suffix)
+        .map_err(|e| format!("decode error: {}", e))?;
+    Ok(decoded)
+}
+
+// --------------------- Streaming ASCII-wrapped codec (std only) ---------------------
+
+/// Streaming counterpart of [`encode_bytes_ascii_wrapped`].
+/// Writes `prefix`, then 3-digit decimal groups (`per_line` per line) as bytes
+/// arrive, then `suffix` on [`AsciiWrappedWriter::finish`]. The output is identical
+/// to the in-memory function whatever the write sizes are.
+///
+/// Only the decimal prefix/suffix format is handled; for labeled
+/// `-----BEGIN BITMASHER ...-----` blocks use `armor::Armor`. That is why this
+/// is not called `ArmorWriter`: it wraps the ASCII codec, not `armor::Armor`.
+#[cfg(feature = "std")]
+pub struct AsciiWrappedWriter<W: std::io::Write> {
+    inner: W,
+    per_line: usize,
+    suffix: String,
+    prefix: Option<String>,
+    count: usize,
+    scratch: Vec<u8>,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> AsciiWrappedWriter<W> {
+    pub fn new(inner: W, per_line: usize, prefix: &str, suffix: &str) -> Self {
+        assert!(per_line >= 1, "per_line must be >= 1");
+        Self {
+            inner,
+            per_line,
+            suffix: suffix.into(),
+            prefix: Some(prefix.into()),
+            count: 0,
+            scratch: Vec::new(),
+        }
+    }
+
+    fn write_prefix(&mut self) -> std::io::Result<()> {
+        if let Some(prefix) = self.prefix.take() {
+            self.inner.write_all(prefix.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Write the suffix, flush and return the inner writer.
+    pub fn finish(mut self) -> std::io::Result<W> {
+        self.write_prefix()?;
+        self.inner.write_all(self.suffix.as_bytes())?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> std::io::Write for AsciiWrappedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write_prefix()?;
+        self.scratch.clear();
+        for &b in buf {
+            if self.count > 0 {
+                self.scratch.push(if self.count.is_multiple_of(self.per_line) { b'\n' } else { b' ' });
+            }
+            self.scratch.extend_from_slice(&[b'0' + b / 100, b'0' + b / 10 % 10, b'0' + b % 10]);
+            self.count += 1;
+        }
+        self.inner.write_all(&self.scratch)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Streaming counterpart of [`decode_bytes_ascii_wrapped`].
+/// Checks `prefix`, yields decoded bytes as tokens are parsed, and checks
+/// `suffix` at end of input. Any ASCII whitespace separates tokens.
+/// Malformed input surfaces as `io::ErrorKind::InvalidData`, at the latest
+/// once a token grows past 3 characters, so memory stays bounded.
+///
+/// Named after the ASCII-wrapped format it reads, not `armor::Armor`
+/// (see [`AsciiWrappedWriter`]), hence not `ArmorReader`.
+#[cfg(feature = "std")]
+pub struct AsciiWrappedReader<R: std::io::Read> {
+    inner: R,
+    prefix: Option<String>,
+    suffix: String,
+    /// raw text read but not yet parsed
+    pending: Vec<u8>,
+    /// decoded bytes not yet returned
+    decoded: std::collections::VecDeque<u8>,
+    token_index: usize,
+    done: bool,
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> AsciiWrappedReader<R> {
+    pub fn new(inner: R, prefix: &str, suffix: &str) -> Self {
+        Self {
+            inner,
+            prefix: Some(prefix.into()),
+            suffix: suffix.into(),
+            pending: Vec::new(),
+            decoded: std::collections::VecDeque::new(),
+            token_index: 0,
+            done: false,
+        }
+    }
+
+    fn invalid(msg: String) -> std::io::Error {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, msg)
+    }
+
+    /// Parse complete tokens in `pending[..limit]`; with `last`, the final
+    /// token does not need a trailing separator.
+    fn parse(&mut self, limit: usize, last: bool) -> std::io::Result<()> {
+        let region = &self.pending[..limit];
+        let mut consumed = 0;
+        let mut pos = 0;
+        while pos < region.len() {
+            if region[pos].is_ascii_whitespace() {
+                pos += 1;
+                consumed = pos;
+                continue;
+            }
+            let end = match region[pos..].iter().position(|b| b.is_ascii_whitespace()) {
+                Some(off) => pos + off,
+                None if last => region.len(),
+                // too long already: fail now instead of buffering an unbounded token
+                None if region.len() - pos > 3 => {
+                    return Err(Self::invalid(format!("token {}: token is not 3 characters", self.token_index)));
+                }
+                None => break,
+            };
+            let token = &region[pos..end];
+            let idx = self.token_index;
+            if token.len() != 3 {
+                return Err(Self::invalid(format!("token {idx}: token is not 3 characters")));
+            }
+            if !token.iter().all(|b| b.is_ascii_digit()) {
+                return Err(Self::invalid(format!("token {idx}: invalid decimal number")));
+            }
+            let val = token.iter().fold(0u32, |v, &d| v * 10 + (d - b'0') as u32);
+            if val > 255 {
+                return Err(Self::invalid(format!("token {idx}: value out of range 0–255")));
+            }
+            self.decoded.push_back(val as u8);
+            self.token_index += 1;
+            pos = end;
+            consumed = pos;
+        }
+        self.pending.drain(..consumed);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> std::io::Read for AsciiWrappedReader<R> {
+    fn read(&mut self, out: &mut [u8]) -> std::io::Result<usize> {
+        let mut chunk = [0u8; 4096];
+        while self.decoded.is_empty() && !self.done {
+            let n = self.inner.read(&mut chunk)?;
+            let eof = n == 0;
+            self.pending.extend_from_slice(&chunk[..n]);
+
+            if let Some(prefix) = &self.prefix {
+                let len = prefix.len().min(self.pending.len());
+                if self.pending[..len] != prefix.as_bytes()[..len] {
+                    return Err(Self::invalid("missing prefix".into()));
+                }
+                if len < prefix.len() {
+                    if eof {
+                        return Err(Self::invalid("missing prefix".into()));
+                    }
+                    continue;
+                }
+                self.pending.drain(..len);
+                self.prefix = None;
+            }
+
+            if eof {
+                if !self.pending.ends_with(self.suffix.as_bytes()) {
+                    return Err(Self::invalid("missing suffix".into()));
+                }
+                let limit = self.pending.len() - self.suffix.len();
+                self.parse(limit, true)?;
+                self.done = true;
+            } else {
+                // hold back enough bytes to recognise the suffix at EOF
+                let limit = self.pending.len().saturating_sub(self.suffix.len());
+                self.parse(limit, false)?;
+            }
+        }
+
+        let n = out.len().min(self.decoded.len());
+        for (o, b) in out.iter_mut().zip(self.decoded.drain(..n)) {
+            *o = b;
+        }
+        Ok(n)
+    }
+}
@@ -0,0 +1,156 @@
+//! drbg.rs — deterministic random bit generator (HMAC-DRBG, SHA-256)
+//!
+//! NIST SP 800-90A HMAC_DRBG with HMAC-SHA256, no prediction resistance.
+//! Seeded from a key and a personalization string, it produces the same
+//! byte stream every time, so anything that takes a caller-supplied RNG
+//! (`csprng_key_same_len`, `interleave_with_rng` and friends,
+//! `export_key_password_protected_with_rng`, the `*_with_rng` pipelines)
+//! becomes bit-exact reproducible: golden tests, audits, replays.
+//!
+//! The output is only as secret as the seed key. Use a full-entropy key of
+//! at least 32 bytes, and keep `OsRng` for production artifacts unless you
+//! need them reproducible.
+//!
+//! Each `fill_bytes` call is one SP 800-90A generate request (split into
+//! requests of at most [`MAX_REQUEST_BYTES`]); `next_u32`/`next_u64` are
+//! generate requests of 4/8 bytes, little-endian.
+
+use core::fmt;
+use core::num::NonZeroU32;
+
+use rand::{CryptoRng, Error, RngCore};
+use ring::hmac;
+use zeroize::Zeroize;
+
+/// Output length of HMAC-SHA256.
+const OUT_LEN: usize = 32;
+
+/// Largest single generate request allowed by SP 800-90A (2^19 bits).
+pub const MAX_REQUEST_BYTES: usize = 1 << 16;
+
+/// Generate requests allowed between reseeds (SP 800-90A: 2^48).
+const RESEED_INTERVAL: u64 = 1 << 48;
+
+/// `rand::Error` code reported once the reseed interval is exhausted.
+const RESEED_REQUIRED: NonZeroU32 = NonZeroU32::new(Error::CUSTOM_START).unwrap();
+
+/// HMAC-DRBG over HMAC-SHA256; see the module docs.
+#[derive(Clone)]
+pub struct HmacDrbg {
+    k: [u8; OUT_LEN],
+    v: [u8; OUT_LEN],
+    reseed_counter: u64,
+}
+
+impl HmacDrbg {
+    /// Instantiate from `key` (the entropy input) and a `personalization`
+    /// string that separates uses of the same key, e.g. `b"golden:chaff"`.
+    pub fn new(key: &[u8], personalization: &[u8]) -> Self {
+        Self::with_nonce(key, &[], personalization)
+    }
+
+    /// Instantiate with an explicit SP 800-90A nonce (as in the CAVP vectors).
+    pub fn with_nonce(entropy: &[u8], nonce: &[u8], personalization: &[u8]) -> Self {
+        let mut drbg = HmacDrbg { k: [0x00; OUT_LEN], v: [0x01; OUT_LEN], reseed_counter: 1 };
+        drbg.update(&[entropy, nonce, personalization]);
+        drbg
+    }
+
+    /// Mix in fresh `entropy` (and optional `additional` input).
+    pub fn reseed(&mut self, entropy: &[u8], additional: &[u8]) {
+        self.update(&[entropy, additional]);
+        self.reseed_counter = 1;
+    }
+
+    /// One generate request: fill `out` (at most [`MAX_REQUEST_BYTES`]),
+    /// mixing in `additional` if non-empty.
+    pub fn generate(&mut self, out: &mut [u8], additional: &[u8]) -> Result<(), &'static str> {
+        if out.len() > MAX_REQUEST_BYTES {
+            return Err("DRBG request too large");
+        }
+        if self.reseed_counter > RESEED_INTERVAL {
+            return Err("DRBG reseed required");
+        }
+        if !additional.is_empty() {
+            self.update(&[additional]);
+        }
+        for chunk in out.chunks_mut(OUT_LEN) {
+            self.v = self.hmac(&[&self.v]);
+            chunk.copy_from_slice(&self.v[..chunk.len()]);
+        }
+        self.update(&[additional]);
+        self.reseed_counter += 1;
+        Ok(())
+    }
+
+    /// HMAC_DRBG_Update; `data` is the concatenation of the slices.
+    fn update(&mut self, data: &[&[u8]]) {
+        let empty = data.iter().all(|d| d.is_empty());
+        for round in [0x00u8, 0x01] {
+            if round == 0x01 && empty {
+                break;
+            }
+            let v = self.v;
+            let mut parts: [&[u8]; 5] = [&v, &[round], &[], &[], &[]];
+            for (slot, d) in parts[2..].iter_mut().zip(data) {
+                *slot = d;
+            }
+            self.k = self.hmac(&parts);
+            self.v = self.hmac(&[&self.v]);
+        }
+    }
+
+    fn hmac(&self, parts: &[&[u8]]) -> [u8; OUT_LEN] {
+        let key = hmac::Key::new(hmac::HMAC_SHA256, &self.k);
+        let mut ctx = hmac::Context::with_key(&key);
+        for p in parts {
+            ctx.update(p);
+        }
+        let mut out = [0u8; OUT_LEN];
+        out.copy_from_slice(ctx.sign().as_ref());
+        out
+    }
+}
+
+impl RngCore for HmacDrbg {
+    fn next_u32(&mut self) -> u32 {
+        let mut b = [0u8; 4];
+        self.fill_bytes(&mut b);
+        u32::from_le_bytes(b)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut b = [0u8; 8];
+        self.fill_bytes(&mut b);
+        u64::from_le_bytes(b)
+    }
+
+    /// Panics only after 2^48 requests without a reseed.
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.try_fill_bytes(dest).expect("HMAC-DRBG reseed required")
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        for chunk in dest.chunks_mut(MAX_REQUEST_BYTES) {
+            self.generate(chunk, &[]).map_err(|_| Error::from(RESEED_REQUIRED))?;
+        }
+        Ok(())
+    }
+}
+
+impl CryptoRng for HmacDrbg {}
+
+impl Drop for HmacDrbg {
+    fn drop(&mut self) {
+        self.k.zeroize();
+        self.v.zeroize();
+    }
+}
+
+impl fmt::Debug for HmacDrbg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HmacDrbg")
+            .field("reseed_counter", &self.reseed_counter)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,374 @@
+//! fec.rs — Reed–Solomon forward error correction over GF(256)
+//!
+//! - Field: GF(2^8) with primitive polynomial 0x11D, generator α = 2,
+//!   first consecutive root α^0.
+//! - Codewords are at most 255 bytes: k data bytes + `parity` parity bytes.
+//!   Each codeword corrects `e` errors and `r` erasures as long as 2e + r <= parity.
+//!
+//! Interleaved stream layout for arbitrary-length data (L bytes, B blocks):
+//!
+//! ```text
+//!   B = max(ceil(L / (255 - parity)), ceil(SPREAD_SPAN / parity)), at most L
+//!   [ data (L bytes, unchanged) | parity (B * parity bytes) ]
+//!   stream byte q belongs to block q % B; the parity bytes of block b fill,
+//!   in order, the positions q >= L with q % B == b
+//! ```
+//!
+//! The stream is systematic (the data comes first, as is), and neighbouring
+//! bytes belong to different blocks: any [`SPREAD_SPAN`] consecutive bytes
+//! (one damaged armor line) put at most `parity` erasures in each block, so
+//! a single lost line is always repairable once L >= SPREAD_SPAN / parity.
+
+extern crate alloc;
+
+use alloc::{format, string::String, vec, vec::Vec};
+
+const PRIM_POLY: u16 = 0x11D;
+
+/// Largest codeword.
+pub const MAX_CODEWORD: usize = 255;
+
+/// Burst length the layout always spreads thin enough to repair: the bytes
+/// of one 64-character Base64 armor line.
+pub const SPREAD_SPAN: usize = 48;
+
+struct Gf {
+    exp: [u8; 512],
+    log: [u8; 256],
+}
+
+const GF: Gf = build_tables();
+
+const fn build_tables() -> Gf {
+    let mut exp = [0u8; 512];
+    let mut log = [0u8; 256];
+    let mut x: u16 = 1;
+    let mut i = 0;
+    while i < 255 {
+        exp[i] = x as u8;
+        log[x as usize] = i as u8;
+        x <<= 1;
+        if x & 0x100 != 0 {
+            x ^= PRIM_POLY;
+        }
+        i += 1;
+    }
+    while i < 512 {
+        exp[i] = exp[i - 255];
+        i += 1;
+    }
+    Gf { exp, log }
+}
+
+#[inline]
+fn gf_mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    GF.exp[GF.log[a as usize] as usize + GF.log[b as usize] as usize]
+}
+
+#[inline]
+fn gf_div(a: u8, b: u8) -> u8 {
+    assert!(b != 0, "GF(256) division by zero");
+    if a == 0 {
+        return 0;
+    }
+    GF.exp[(GF.log[a as usize] as usize + 255 - GF.log[b as usize] as usize) % 255]
+}
+
+/// α^power for any (possibly negative) power.
+#[inline]
+fn gf_alpha_pow(power: i32) -> u8 {
+    GF.exp[power.rem_euclid(255) as usize]
+}
+
+#[inline]
+fn gf_inverse(a: u8) -> u8 {
+    gf_div(1, a)
+}
+
+// Polynomials are stored highest degree first.
+
+fn poly_scale(p: &[u8], x: u8) -> Vec<u8> {
+    p.iter().map(|&c| gf_mul(c, x)).collect()
+}
+
+fn poly_add(p: &[u8], q: &[u8]) -> Vec<u8> {
+    let n = p.len().max(q.len());
+    let mut r = vec![0u8; n];
+    for (i, &c) in p.iter().enumerate() {
+        r[i + n - p.len()] = c;
+    }
+    for (i, &c) in q.iter().enumerate() {
+        r[i + n - q.len()] ^= c;
+    }
+    r
+}
+
+fn poly_mul(p: &[u8], q: &[u8]) -> Vec<u8> {
+    let mut r = vec![0u8; p.len() + q.len() - 1];
+    for (j, &qj) in q.iter().enumerate() {
+        for (i, &pi) in p.iter().enumerate() {
+            r[i + j] ^= gf_mul(pi, qj);
+        }
+    }
+    r
+}
+
+fn poly_eval(p: &[u8], x: u8) -> u8 {
+    p.iter().skip(1).fold(p[0], |y, &c| gf_mul(y, x) ^ c)
+}
+
+fn generator_poly(parity: usize) -> Vec<u8> {
+    let mut g = vec![1u8];
+    for i in 0..parity {
+        g = poly_mul(&g, &[1, gf_alpha_pow(i as i32)]);
+    }
+    g
+}
+
+/// Parity bytes for one codeword (`data.len() + parity <= 255`).
+fn encode_block(data: &[u8], generator: &[u8]) -> Vec<u8> {
+    let parity = generator.len() - 1;
+    let mut buf = data.to_vec();
+    buf.resize(data.len() + parity, 0);
+    for i in 0..data.len() {
+        let coef = buf[i];
+        if coef != 0 {
+            for (j, &g) in generator.iter().enumerate().skip(1) {
+                buf[i + j] ^= gf_mul(g, coef);
+            }
+        }
+    }
+    buf.split_off(data.len())
+}
+
+fn syndromes(msg: &[u8], parity: usize) -> Vec<u8> {
+    // leading 0 keeps the indexing of the classic formulation
+    let mut s = vec![0u8; parity + 1];
+    for i in 0..parity {
+        s[i + 1] = poly_eval(msg, gf_alpha_pow(i as i32));
+    }
+    s
+}
+
+fn forney_syndromes(synd: &[u8], erasures: &[usize], n: usize) -> Vec<u8> {
+    let mut fsynd = synd[1..].to_vec();
+    for &p in erasures {
+        let x = gf_alpha_pow((n - 1 - p) as i32);
+        for j in 0..fsynd.len() - 1 {
+            fsynd[j] = gf_mul(fsynd[j], x) ^ fsynd[j + 1];
+        }
+    }
+    fsynd
+}
+
+/// Berlekamp–Massey over the Forney syndromes.
+fn error_locator(fsynd: &[u8], parity: usize, erase_count: usize) -> Result<Vec<u8>, String> {
+    let mut err_loc = vec![1u8];
+    let mut old_loc = vec![1u8];
+    for k in 0..parity - erase_count {
+        let mut delta = fsynd[k];
+        for j in 1..err_loc.len() {
+            delta ^= gf_mul(err_loc[err_loc.len() - 1 - j], fsynd[k - j]);
+        }
+        old_loc.push(0);
+        if delta != 0 {
+            if old_loc.len() > err_loc.len() {
+                let new_loc = poly_scale(&old_loc, delta);
+                old_loc = poly_scale(&err_loc, gf_inverse(delta));
+                err_loc = new_loc;
+            }
+            err_loc = poly_add(&err_loc, &poly_scale(&old_loc, delta));
+        }
+    }
+    let lead = err_loc.iter().take_while(|&&c| c == 0).count();
+    err_loc.drain(..lead);
+    let errs = err_loc.len() - 1;
+    if errs * 2 + erase_count > parity {
+        return Err("too many errors to correct".into());
+    }
+    Ok(err_loc)
+}
+
+/// Chien search: positions (in the codeword) of the roots of `err_loc`.
+fn find_errors(err_loc: &[u8], n: usize) -> Result<Vec<usize>, String> {
+    let rev: Vec<u8> = err_loc.iter().rev().copied().collect();
+    let errs = err_loc.len() - 1;
+    let pos: Vec<usize> = (0..n)
+        .filter(|&i| poly_eval(&rev, gf_alpha_pow(i as i32)) == 0)
+        .map(|i| n - 1 - i)
+        .collect();
+    if pos.len() != errs {
+        return Err("too many errors to correct (locator roots do not match)".into());
+    }
+    Ok(pos)
+}
+
+/// Forney algorithm: compute and apply errata magnitudes at `pos`.
+fn correct_errata(msg: &mut [u8], synd: &[u8], pos: &[usize]) {
+    let n = msg.len();
+    let coef_pos: Vec<usize> = pos.iter().map(|&p| n - 1 - p).collect();
+
+    let mut err_loc = vec![1u8];
+    for &c in &coef_pos {
+        err_loc = poly_mul(&err_loc, &poly_add(&[1], &[gf_alpha_pow(c as i32), 0]));
+    }
+
+    // evaluator = (S(x) * Λ(x)) mod x^(deg Λ + 1), with S reversed
+    let synd_rev: Vec<u8> = synd.iter().rev().copied().collect();
+    let prod = poly_mul(&synd_rev, &err_loc);
+    let keep = err_loc.len();
+    let err_eval = prod[prod.len() - keep..].to_vec();
+
+    let x: Vec<u8> = coef_pos.iter().map(|&c| gf_alpha_pow(c as i32)).collect();
+    for (i, &xi) in x.iter().enumerate() {
+        let xi_inv = gf_inverse(xi);
+        let mut loc_prime = 1u8;
+        for (j, &xj) in x.iter().enumerate() {
+            if j != i {
+                loc_prime = gf_mul(loc_prime, 1 ^ gf_mul(xi_inv, xj));
+            }
+        }
+        let y = gf_mul(xi, poly_eval(&err_eval, xi_inv));
+        msg[pos[i]] ^= gf_div(y, loc_prime);
+    }
+}
+
+/// Correct one codeword in place. Returns the positions that were changed.
+fn decode_block(msg: &mut [u8], parity: usize, erasures: &[usize]) -> Result<Vec<usize>, String> {
+    if erasures.len() > parity {
+        return Err(format!("{} erasures exceed {parity} parity bytes", erasures.len()));
+    }
+    let original = msg.to_vec();
+    for &e in erasures {
+        msg[e] = 0;
+    }
+    let synd = syndromes(msg, parity);
+    if synd.iter().all(|&s| s == 0) {
+        return Ok(changed(&original, msg));
+    }
+
+    let fsynd = forney_syndromes(&synd, erasures, msg.len());
+    let err_loc = error_locator(&fsynd, parity, erasures.len())?;
+    let mut errata: Vec<usize> = erasures.to_vec();
+    errata.extend(find_errors(&err_loc, msg.len())?);
+    correct_errata(msg, &synd, &errata);
+
+    if syndromes(msg, parity).iter().any(|&s| s != 0) {
+        return Err("could not correct codeword".into());
+    }
+    Ok(changed(&original, msg))
+}
+
+fn changed(a: &[u8], b: &[u8]) -> Vec<usize> {
+    (0..a.len()).filter(|&i| a[i] != b[i]).collect()
+}
+
+fn check_parity(parity: usize) -> Result<(), String> {
+    if parity == 0 || parity >= MAX_CODEWORD {
+        return Err(format!("parity must be in 1..{MAX_CODEWORD}, got {parity}"));
+    }
+    Ok(())
+}
+
+/// `parity` must already be checked.
+fn block_count(data_len: usize, parity: usize) -> usize {
+    data_len
+        .div_ceil(MAX_CODEWORD - parity)
+        .max(SPREAD_SPAN.div_ceil(parity))
+        .min(data_len)
+}
+
+/// Stream position of parity byte `j` of block `b` (see module docs).
+fn parity_pos(data_len: usize, blocks: usize, b: usize, j: usize) -> usize {
+    data_len + (b + blocks - data_len % blocks) % blocks + j * blocks
+}
+
+/// Length of the interleaved stream for `data_len` data bytes. Fails on an
+/// invalid `parity` or a length that overflows, e.g. from untrusted headers.
+pub fn stream_len(data_len: usize, parity: usize) -> Result<usize, String> {
+    check_parity(parity)?;
+    block_count(data_len, parity)
+        .checked_mul(parity)
+        .and_then(|p| p.checked_add(data_len))
+        .ok_or_else(|| format!("FEC stream for {data_len} data bytes is too long"))
+}
+
+/// Append interleaved Reed–Solomon parity to `data` (see module docs).
+pub fn rs_encode(data: &[u8], parity: usize) -> Result<Vec<u8>, String> {
+    let total = stream_len(data.len(), parity)?;
+    let blocks = block_count(data.len(), parity);
+    let generator = generator_poly(parity);
+
+    let mut out = data.to_vec();
+    out.resize(total, 0);
+    for b in 0..blocks {
+        let block: Vec<u8> = data.iter().skip(b).step_by(blocks).copied().collect();
+        for (j, p) in encode_block(&block, &generator).into_iter().enumerate() {
+            out[parity_pos(data.len(), blocks, b, j)] = p;
+        }
+    }
+    Ok(out)
+}
+
+/// What the decoder repaired.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FecReport {
+    /// Stream positions whose value was changed (positions < data length are data bytes).
+    pub corrected: Vec<usize>,
+    /// Stream positions that were declared erased by the caller.
+    pub erased: Vec<usize>,
+}
+
+impl FecReport {
+    /// True if nothing had to be repaired.
+    pub fn is_clean(&self) -> bool {
+        self.corrected.is_empty() && self.erased.is_empty()
+    }
+}
+
+/// Correct an interleaved stream produced by [`rs_encode`] and return the data.
+/// `erasures` are stream positions known to be bad (their content is ignored).
+pub fn rs_decode(
+    stream: &[u8],
+    data_len: usize,
+    parity: usize,
+    erasures: &[usize],
+) -> Result<(Vec<u8>, FecReport), String> {
+    let total = stream_len(data_len, parity)?;
+    if stream.len() != total {
+        return Err(format!("stream length {} != expected {total}", stream.len()));
+    }
+    let blocks = block_count(data_len, parity);
+    let mut out = stream.to_vec();
+    let mut report = FecReport { corrected: Vec::new(), erased: erasures.to_vec() };
+    report.erased.sort_unstable();
+    report.erased.dedup();
+
+    for b in 0..blocks {
+        // stream positions making up codeword b, in codeword order
+        let positions: Vec<usize> = (b..data_len)
+            .step_by(blocks)
+            .chain((0..parity).map(|j| parity_pos(data_len, blocks, b, j)))
+            .collect();
+        let mut word: Vec<u8> = positions.iter().map(|&p| out[p]).collect();
+        let word_erasures: Vec<usize> = positions
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| report.erased.binary_search(p).is_ok())
+            .map(|(i, _)| i)
+            .collect();
+
+        let fixed = decode_block(&mut word, parity, &word_erasures)
+            .map_err(|e| format!("block {b}: {e}"))?;
+        for i in fixed {
+            out[positions[i]] = word[i];
+            report.corrected.push(positions[i]);
+        }
+    }
+    report.corrected.sort_unstable();
+    out.truncate(data_len);
+    Ok((out, report))
+}
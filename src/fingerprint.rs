@@ -0,0 +1,127 @@
+//! fingerprint.rs — stable key fingerprints and short Key-Ids
+//!
+//! A fingerprint is SHA-256 over a canonical encoding of the key,
+//! truncated to 20 bytes (`lp(x)` = u32be(len) ‖ x):
+//!
+//! ```text
+//! SHA-256("bitmasher:fingerprint:v1\0" ‖ lp(algorithm) ‖ lp(key))[..20]
+//! ```
+//!
+//! `algorithm` is `"symmetric"` for secret keys such as the v2 `hkdf_key`,
+//! or the `Algorithm` header value of a public key (`X25519`, `Ed25519`),
+//! so equal bytes used as different key types never share a fingerprint.
+//! The Key-Id is the first 8 bytes, written as 16 uppercase hex digits in
+//! `Key-Id` armor headers.
+//!
+//! Fingerprints of secret keys are safe to publish only for high-entropy
+//! keys (derived or random), which is what this crate produces.
+
+extern crate alloc;
+
+use alloc::{format, string::String};
+use core::fmt;
+use core::str::FromStr;
+
+use ring::digest;
+
+const FINGERPRINT_CONTEXT: &[u8] = b"bitmasher:fingerprint:v1\0";
+/// `algorithm` of symmetric (secret) keys.
+pub const ALGORITHM_SYMMETRIC: &str = "symmetric";
+
+pub const FINGERPRINT_LEN: usize = 20;
+pub const KEY_ID_LEN: usize = 8;
+
+/// Truncated SHA-256 over a key; see the module docs.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fingerprint([u8; FINGERPRINT_LEN]);
+
+/// Short key identifier: the first 8 bytes of the [`Fingerprint`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyId([u8; KEY_ID_LEN]);
+
+impl Fingerprint {
+    /// Fingerprint of `key` used as `algorithm`.
+    pub fn new(algorithm: &str, key: &[u8]) -> Self {
+        let mut ctx = digest::Context::new(&digest::SHA256);
+        ctx.update(FINGERPRINT_CONTEXT);
+        for part in [algorithm.as_bytes(), key] {
+            ctx.update(&(part.len() as u32).to_be_bytes());
+            ctx.update(part);
+        }
+        let mut out = [0u8; FINGERPRINT_LEN];
+        out.copy_from_slice(&ctx.finish().as_ref()[..FINGERPRINT_LEN]);
+        Fingerprint(out)
+    }
+
+    /// Fingerprint of a secret (symmetric) key.
+    pub fn of_symmetric(key: &[u8]) -> Self {
+        Self::new(ALGORITHM_SYMMETRIC, key)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; FINGERPRINT_LEN] {
+        &self.0
+    }
+
+    pub fn key_id(&self) -> KeyId {
+        let mut id = [0u8; KEY_ID_LEN];
+        id.copy_from_slice(&self.0[..KEY_ID_LEN]);
+        KeyId(id)
+    }
+}
+
+/// Groups of 4 hex digits: `1A2B 3C4D ...`.
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, pair) in self.0.chunks(2).enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{:02X}{:02X}", pair[0], pair[1])?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Fingerprint({self})")
+    }
+}
+
+impl KeyId {
+    pub fn as_bytes(&self) -> &[u8; KEY_ID_LEN] {
+        &self.0
+    }
+}
+
+/// 16 uppercase hex digits, as in `Key-Id` headers.
+impl fmt::Display for KeyId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for b in &self.0 {
+            write!(f, "{b:02X}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for KeyId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "KeyId({self})")
+    }
+}
+
+/// Parses 16 hex digits (either case).
+impl FromStr for KeyId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        if s.len() != 2 * KEY_ID_LEN || !s.bytes().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("invalid Key-Id '{s}': expected {} hex digits", 2 * KEY_ID_LEN));
+        }
+        let mut id = [0u8; KEY_ID_LEN];
+        for (i, b) in id.iter_mut().enumerate() {
+            *b = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).map_err(|_| format!("invalid Key-Id '{s}'"))?;
+        }
+        Ok(KeyId(id))
+    }
+}
@@ -1,11 +1,24 @@
+extern crate alloc;
+
+use alloc::{format, string::String, vec::Vec};
+
+use rand::{CryptoRng, RngCore};
+use ring::hmac;
+
+use crate::BitVec;
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+#[cfg(feature = "std")]
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Tiny, dependency-free PRNG (XorShift64) we can seed from system time.
 /// This avoids pulling in external crates for randomness.
+#[cfg(feature = "std")]
 #[derive(Clone)]
 struct XorShift64 {
     state: u64,
 }
+#[cfg(feature = "std")]
 impl XorShift64 {
     fn new(seed: u64) -> Self {
         // Avoid zero state
@@ -25,6 +38,7 @@ impl XorShift64 {
 }
 
 /// Generate a seed from system time.
+#[cfg(feature = "std")]
 fn seed_from_time() -> u64 {
     let nanos = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -35,30 +49,44 @@ fn seed_from_time() -> u64 {
 }
 
 /// 1) Interleave original UTF-8 bytes with one random byte between each original byte.
-/// Output layout: [o0, r0, o1, r1, ..., o(n-1)]
-/// (No trailing random byte after the last original.)
+///    Output layout: [o0, r0, o1, r1, ..., o(n-1)]
+///    (No trailing random byte after the last original.)
 ///
 /// Returns binary Vec<u8>. If you need a printable form, use the hex variant below.
+///
+/// Requires `std` (filler seeded from system time); see [`interleave_with_rng`].
+#[cfg(feature = "std")]
 pub fn interleave_with_random_bytes(input: &str) -> Vec<u8> {
+    let mut rng = XorShift64::new(seed_from_time());
+    interleave_with_filler(input, || rng.next_u8())
+}
+
+/// Same layout as [`interleave_with_random_bytes`], with filler bytes drawn
+/// from a caller-supplied `rng` (`no_std` friendly).
+pub fn interleave_with_rng<R: RngCore + ?Sized>(input: &str, rng: &mut R) -> Vec<u8> {
+    interleave_with_filler(input, || rng.next_u32() as u8)
+}
+
+fn interleave_with_filler(input: &str, mut filler: impl FnMut() -> u8) -> Vec<u8> {
     let bytes = input.as_bytes();
     let n = bytes.len();
     if n == 0 {
         return Vec::new();
     }
 
-    let mut rng = XorShift64::new(seed_from_time());
     // Length: n originals + (n-1) randoms
     let mut out = Vec::with_capacity(n + (n - 1));
     for (i, &b) in bytes.iter().enumerate() {
         out.push(b);
         if i + 1 < n {
-            out.push(rng.next_u8());
+            out.push(filler());
         }
     }
     out
 }
 
 /// Hex-encode helper (uppercase, no 0x prefix).
+#[cfg(feature = "std")]
 fn to_hex(bytes: &[u8]) -> String {
     const HEX: &[u8; 16] = b"0123456789ABCDEF";
     let mut s = String::with_capacity(bytes.len() * 2);
@@ -80,7 +108,7 @@ fn from_hex(s: &str) -> Result<Vec<u8>, String> {
         }
     }
     let bytes = s.as_bytes();
-    if bytes.len() % 2 != 0 {
+    if !bytes.len().is_multiple_of(2) {
         return Err("hex string length must be even".into());
     }
     let mut out = Vec::with_capacity(bytes.len() / 2);
@@ -93,20 +121,21 @@ fn from_hex(s: &str) -> Result<Vec<u8>, String> {
 }
 
 /// Convenience: interleave and return a HEX STRING (printable, UTF-8 safe).
+#[cfg(feature = "std")]
 pub fn interleave_with_random_bytes_hex(input: &str) -> String {
     let bin = interleave_with_random_bytes(input);
     to_hex(&bin)
 }
 
 /// 2) Deinterleave previously generated binary buffer.
-/// Inverse of `interleave_with_random_bytes`.
-/// Keeps original bytes at even indices (0, 2, 4, ...).
-/// Returns the original UTF-8 string.
+///    Inverse of `interleave_with_random_bytes`.
+///    Keeps original bytes at even indices (0, 2, 4, ...).
+///    Returns the original UTF-8 string.
 pub fn deinterleave_original_bytes(interleaved: &[u8]) -> Result<String, String> {
     if interleaved.is_empty() {
         return Ok(String::new());
     }
-    let mut orig = Vec::with_capacity((interleaved.len() + 1) / 2);
+    let mut orig = Vec::with_capacity(interleaved.len().div_ceil(2));
     for (i, &b) in interleaved.iter().enumerate() {
         if i % 2 == 0 {
             orig.push(b);
@@ -120,3 +149,624 @@ pub fn deinterleave_original_bytes_from_hex(hex: &str) -> Result<String, String>
     let bin = from_hex(hex)?;
     deinterleave_original_bytes(&bin)
 }
+
+/// Keyed pseudorandom stream: HMAC-SHA256(key, label || counter) blocks.
+/// Used to place original bytes at secret positions in the keyed interleave.
+struct KeyStream {
+    key: hmac::Key,
+    label: &'static [u8],
+    counter: u64,
+    block: [u8; 32],
+    pos: usize,
+}
+impl KeyStream {
+    fn new(key: &[u8], label: &'static [u8]) -> Self {
+        Self {
+            key: hmac::Key::new(hmac::HMAC_SHA256, key),
+            label,
+            counter: 0,
+            block: [0u8; 32],
+            pos: 32,
+        }
+    }
+    fn refill(&mut self) {
+        let mut ctx = hmac::Context::with_key(&self.key);
+        ctx.update(self.label);
+        ctx.update(&self.counter.to_be_bytes());
+        self.block.copy_from_slice(ctx.sign().as_ref());
+        self.counter += 1;
+        self.pos = 0;
+    }
+    fn next_u64(&mut self) -> u64 {
+        if self.pos + 8 > self.block.len() {
+            self.refill();
+        }
+        let mut b = [0u8; 8];
+        b.copy_from_slice(&self.block[self.pos..self.pos + 8]);
+        self.pos += 8;
+        u64::from_be_bytes(b)
+    }
+}
+
+/// Domain-separation labels for keyed interleave positions.
+const KEYED_POSITIONS_LABEL: &[u8] = b"bitmasher:interleave:keyed-positions";
+const KEYED_BIT_POSITIONS_LABEL: &[u8] = b"bitmasher:interleave:keyed-bit-positions";
+
+/// Walks the slots of a keyed interleave and decides, for each one,
+/// whether it carries an original byte (`true`) or chaff (`false`).
+/// Selection sampling: with `r` originals left over `m` slots, a slot is
+/// original with probability r/m, so exactly `originals` slots are picked
+/// and their relative order is preserved.
+struct KeyedSlots {
+    stream: KeyStream,
+    originals_left: usize,
+    slots_left: usize,
+}
+impl KeyedSlots {
+    fn new(key: &[u8], label: &'static [u8], originals: usize, slots: usize) -> Self {
+        Self {
+            stream: KeyStream::new(key, label),
+            originals_left: originals,
+            slots_left: slots,
+        }
+    }
+}
+impl Iterator for KeyedSlots {
+    type Item = bool;
+    fn next(&mut self) -> Option<bool> {
+        if self.slots_left == 0 {
+            return None;
+        }
+        let pick = (self.stream.next_u64() % self.slots_left as u64) < self.originals_left as u64;
+        self.slots_left -= 1;
+        if pick {
+            self.originals_left -= 1;
+        }
+        Some(pick)
+    }
+}
+
+/// Number of slots for `n` originals in the interleave layouts (n originals + n-1 chaff).
+#[inline]
+fn slot_count(n: usize) -> usize {
+    if n == 0 { 0 } else { 2 * n - 1 }
+}
+
+/// 3) Keyed interleave: same size as `interleave_with_random_bytes` (n originals
+///    and n-1 random bytes), but the positions of the original bytes are chosen
+///    pseudorandomly from `key` instead of sitting at even indices.
+///    The original bytes keep their relative order.
+///
+/// `key` should be secret (e.g. an HKDF-derived key); without it the chaff
+/// cannot be told apart from the original bytes by position.
+///
+/// Each filler byte is a copy of a randomly chosen input byte (OS CSPRNG),
+/// so chaff and originals share the same byte-value distribution and ASCII
+/// text cannot be winnowed by value alone. Only single-byte statistics are
+/// matched: byte pairs such as UTF-8 sequences or common bigrams can still
+/// hint at which bytes are original, so treat this as obfuscation, not
+/// encryption.
+#[cfg(feature = "std")]
+pub fn interleave_with_key(input: &str, key: &[u8]) -> Vec<u8> {
+    interleave_with_key_rng(input, key, &mut rand::rngs::OsRng)
+}
+
+/// Same as [`interleave_with_key`], with filler bytes drawn from `rng` (`no_std` friendly).
+pub fn interleave_with_key_rng<R: RngCore + ?Sized>(input: &str, key: &[u8], rng: &mut R) -> Vec<u8> {
+    let bytes = input.as_bytes();
+    let total = slot_count(bytes.len());
+
+    let mut originals = bytes.iter();
+    let mut out = Vec::with_capacity(total);
+    for is_original in KeyedSlots::new(key, KEYED_POSITIONS_LABEL, bytes.len(), total) {
+        if is_original {
+            // KeyedSlots picks exactly bytes.len() original slots
+            out.push(*originals.next().expect("keyed slot count mismatch"));
+        } else {
+            // resample the payload so chaff values follow its distribution
+            out.push(bytes[(rng.next_u64() % bytes.len() as u64) as usize]);
+        }
+    }
+    out
+}
+
+/// Convenience: keyed interleave and return a HEX STRING.
+#[cfg(feature = "std")]
+pub fn interleave_with_key_hex(input: &str, key: &[u8]) -> String {
+    to_hex(&interleave_with_key(input, key))
+}
+
+/// 4) Inverse of `interleave_with_key`.
+///    Recomputes the original positions from `key` and returns the original UTF-8 string.
+///    A wrong key selects the wrong bytes and most likely yields invalid UTF-8 or garbage.
+pub fn deinterleave_with_key(interleaved: &[u8], key: &[u8]) -> Result<String, String> {
+    if interleaved.is_empty() {
+        return Ok(String::new());
+    }
+    if interleaved.len().is_multiple_of(2) {
+        return Err(format!(
+            "keyed interleave length must be odd (2n-1), got {}",
+            interleaved.len()
+        ));
+    }
+    let n = interleaved.len().div_ceil(2);
+    let orig: Vec<u8> = KeyedSlots::new(key, KEYED_POSITIONS_LABEL, n, interleaved.len())
+        .zip(interleaved.iter())
+        .filter_map(|(is_original, &b)| is_original.then_some(b))
+        .collect();
+    String::from_utf8(orig).map_err(|e| format!("not valid UTF-8: {e}"))
+}
+
+/// Convenience: keyed deinterleave from a HEX STRING input.
+pub fn deinterleave_with_key_from_hex(hex: &str, key: &[u8]) -> Result<String, String> {
+    let bin = from_hex(hex)?;
+    deinterleave_with_key(&bin, key)
+}
+
+/// 5) Bit-level interleave: after each original bit (except the last) insert
+///    `chaff_per_bit` random bits.
+///    Output layout for chaff_per_bit = 1: [b0, r0, b1, r1, ..., b(m-1)], m = 8 * input.len()
+///
+/// Unlike the byte variants, byte boundaries and UTF-8 structure are not preserved
+/// in the output, which makes it a good fit for the `BitArray` rotations.
+#[cfg(feature = "std")]
+pub fn interleave_bits_with_random(input: &str, chaff_per_bit: usize) -> BitVec {
+    interleave_bits_with_rng(input, chaff_per_bit, &mut rand::rngs::OsRng)
+}
+
+/// Same layout as [`interleave_bits_with_random`], with chaff bits drawn
+/// from a caller-supplied `rng` (`no_std` friendly).
+pub fn interleave_bits_with_rng<R: RngCore + ?Sized>(
+    input: &str,
+    chaff_per_bit: usize,
+    rng: &mut R,
+) -> BitVec {
+    interleave_bits_with_filler(input, chaff_per_bit, || rng.next_u32() & 1 != 0)
+}
+
+fn interleave_bits_with_filler(
+    input: &str,
+    chaff_per_bit: usize,
+    mut filler: impl FnMut() -> bool,
+) -> BitVec {
+    let src = BitVec::from_bytes(input.as_bytes(), input.len() * 8);
+    let m = src.bit_len();
+    if m == 0 {
+        return BitVec::new();
+    }
+
+    let mut out = BitVec::with_capacity(m + (m - 1) * chaff_per_bit);
+    for i in 0..m {
+        out.push(src.get_bit(i));
+        if i + 1 < m {
+            for _ in 0..chaff_per_bit {
+                out.push(filler());
+            }
+        }
+    }
+    out
+}
+
+/// 6) Inverse of `interleave_bits_with_random`: keep every (chaff_per_bit + 1)-th bit.
+pub fn deinterleave_bits(bits: &BitVec, chaff_per_bit: usize) -> Result<String, String> {
+    let len = bits.bit_len();
+    if len == 0 {
+        return Ok(String::new());
+    }
+    let stride = chaff_per_bit + 1;
+    if !(len - 1).is_multiple_of(stride) {
+        return Err(format!("bit length {len} does not match chaff ratio {chaff_per_bit}"));
+    }
+    let m = (len - 1) / stride + 1;
+    if !m.is_multiple_of(8) {
+        return Err(format!("recovered {m} bits, not a whole number of bytes"));
+    }
+
+    let mut orig = BitVec::with_capacity(m);
+    for i in (0..len).step_by(stride) {
+        orig.push(bits.get_bit(i));
+    }
+    String::from_utf8(orig.into_bytes()).map_err(|e| format!("not valid UTF-8: {e}"))
+}
+
+/// 7) Keyed bit-level interleave: m original bits + (m-1) random bits, with the
+///    positions of the original bits chosen pseudorandomly from `key`.
+///
+/// Chaff bits come from the OS CSPRNG: predictable chaff would reveal which
+/// positions hold the original bits.
+#[cfg(feature = "std")]
+pub fn interleave_bits_with_key(input: &str, key: &[u8]) -> BitVec {
+    interleave_bits_with_key_rng(input, key, &mut rand::rngs::OsRng)
+}
+
+/// Same as [`interleave_bits_with_key`], with chaff bits drawn from `rng` (`no_std` friendly).
+pub fn interleave_bits_with_key_rng<R: RngCore + ?Sized>(input: &str, key: &[u8], rng: &mut R) -> BitVec {
+    interleave_bits_with_key_filler(input, key, || rng.next_u32() & 1 != 0)
+}
+
+fn interleave_bits_with_key_filler(input: &str, key: &[u8], mut filler: impl FnMut() -> bool) -> BitVec {
+    let src = BitVec::from_bytes(input.as_bytes(), input.len() * 8);
+    let m = src.bit_len();
+    let total = slot_count(m);
+
+    let mut next_original = 0;
+    let mut out = BitVec::with_capacity(total);
+    for is_original in KeyedSlots::new(key, KEYED_BIT_POSITIONS_LABEL, m, total) {
+        if is_original {
+            out.push(src.get_bit(next_original));
+            next_original += 1;
+        } else {
+            out.push(filler());
+        }
+    }
+    out
+}
+
+/// 8) Inverse of `interleave_bits_with_key`.
+pub fn deinterleave_bits_with_key(bits: &BitVec, key: &[u8]) -> Result<String, String> {
+    let len = bits.bit_len();
+    if len == 0 {
+        return Ok(String::new());
+    }
+    if len.is_multiple_of(2) {
+        return Err(format!("keyed bit interleave length must be odd (2m-1), got {len}"));
+    }
+    let m = len.div_ceil(2);
+    if !m.is_multiple_of(8) {
+        return Err(format!("recovered {m} bits, not a whole number of bytes"));
+    }
+
+    let mut orig = BitVec::with_capacity(m);
+    for (i, is_original) in KeyedSlots::new(key, KEYED_BIT_POSITIONS_LABEL, m, len).enumerate() {
+        if is_original {
+            orig.push(bits.get_bit(i));
+        }
+    }
+    String::from_utf8(orig.into_bytes()).map_err(|e| format!("not valid UTF-8: {e}"))
+}
+
+// --------------------- Chaffing and winnowing ---------------------
+
+/// Domain-separation label for chaffing-and-winnowing MAC tags.
+const WINNOW_LABEL: &[u8] = b"bitmasher:interleave:winnow";
+
+/// HMAC-SHA256 tag length.
+pub const WINNOW_TAG_LEN: usize = 32;
+
+/// Packet layout: | serial (u32 BE) | value (u8) | tag (32) |
+pub const WINNOW_PACKET_LEN: usize = 4 + 1 + WINNOW_TAG_LEN;
+
+/// `chaff_per_byte` that covers every byte value in each serial group.
+pub const WINNOW_FULL_CHAFF: usize = 255;
+
+/// MAC input for one packet: label || serial (u32 BE) || value.
+fn winnow_message(serial: u32, value: u8) -> Vec<u8> {
+    let mut m = Vec::with_capacity(WINNOW_LABEL.len() + 5);
+    m.extend_from_slice(WINNOW_LABEL);
+    m.extend_from_slice(&serial.to_be_bytes());
+    m.push(value);
+    m
+}
+
+/// 9) Chaffing (Rivest-style): every original byte becomes a packet
+///    `(serial, value, HMAC-SHA256(key, serial || value))`, and `chaff_per_byte`
+///    chaff packets with the same serial, distinct other values and random tags
+///    are mixed in at random positions within each serial group.
+///
+/// Nothing is encrypted; only a holder of `key` can tell wheat from chaff.
+/// With [`WINNOW_FULL_CHAFF`] (255) every serial carries all 256 byte values
+/// once, so the packets say nothing about the input without the key, as in
+/// Rivest's per-bit scheme. Fewer chaff packets only *authenticate*: chaff
+/// values are uniform while text is not (for ASCII input the printable
+/// candidate is usually the real byte), so they give little confidentiality.
+/// `chaff_per_byte` must be in 1..=255.
+///
+/// Requires `std` (OS CSPRNG); see [`chaff_with_hmac_rng`].
+#[cfg(feature = "std")]
+pub fn chaff_with_hmac(input: &str, key: &[u8], chaff_per_byte: usize) -> Result<Vec<u8>, String> {
+    chaff_with_hmac_rng(input, key, chaff_per_byte, &mut rand::rngs::OsRng)
+}
+
+/// Like [`chaff_with_hmac`], with chaff drawn from a caller-supplied CSPRNG.
+/// Chaff tags must be unpredictable, hence the `CryptoRng` bound.
+pub fn chaff_with_hmac_rng<R: RngCore + CryptoRng + ?Sized>(
+    input: &str,
+    key: &[u8],
+    chaff_per_byte: usize,
+    rng: &mut R,
+) -> Result<Vec<u8>, String> {
+    let bytes = input.as_bytes();
+    if bytes.len() > u32::MAX as usize {
+        return Err("input too long for 32-bit serial numbers".into());
+    }
+    if !(1..=WINNOW_FULL_CHAFF).contains(&chaff_per_byte) {
+        return Err(format!("chaff_per_byte must be in 1..={WINNOW_FULL_CHAFF}, got {chaff_per_byte}"));
+    }
+    let mac_key = hmac::Key::new(hmac::HMAC_SHA256, key);
+
+    let group = chaff_per_byte + 1;
+    let mut out = Vec::with_capacity(bytes.len() * group * WINNOW_PACKET_LEN);
+    fn fill<R: RngCore + ?Sized>(rng: &mut R, buf: &mut [u8]) -> Result<(), String> {
+        rng.try_fill_bytes(buf).map_err(|_| String::from("rng chaff"))
+    }
+    fn draw<R: RngCore + ?Sized>(rng: &mut R, bound: usize) -> Result<usize, String> {
+        let mut b = [0u8; 8];
+        fill(rng, &mut b)?;
+        Ok((u64::from_be_bytes(b) % bound as u64) as usize)
+    }
+    for (serial, &value) in bytes.iter().enumerate() {
+        let serial = serial as u32;
+        let wheat_at = draw(rng, group)?;
+
+        // chaff values: a partial Fisher-Yates shuffle of the 255 other values
+        let mut others: Vec<u8> = (0..=u8::MAX).filter(|&v| v != value).collect();
+        for i in 0..chaff_per_byte {
+            let j = i + draw(rng, others.len() - i)?;
+            others.swap(i, j);
+        }
+        let mut chaff = others[..chaff_per_byte].iter();
+
+        for slot in 0..group {
+            out.extend_from_slice(&serial.to_be_bytes());
+            if slot == wheat_at {
+                out.push(value);
+                out.extend_from_slice(hmac::sign(&mac_key, &winnow_message(serial, value)).as_ref());
+            } else {
+                let mut tag = [0u8; WINNOW_TAG_LEN];
+                fill(rng, &mut tag)?;
+                out.push(*chaff.next().expect("one chaff value per chaff slot"));
+                out.extend_from_slice(&tag);
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// 10) Winnowing: inverse of `chaff_with_hmac`.
+///     Keeps the packets whose tag verifies under `key` and returns the original
+///     UTF-8 string. Fails if any serial has no valid packet, more than one valid
+///     packet, or if serials are out of order.
+pub fn winnow_with_hmac(packets: &[u8], key: &[u8]) -> Result<String, String> {
+    if !packets.len().is_multiple_of(WINNOW_PACKET_LEN) {
+        return Err(format!(
+            "packet stream length {} is not a multiple of {}",
+            packets.len(),
+            WINNOW_PACKET_LEN
+        ));
+    }
+    let mac_key = hmac::Key::new(hmac::HMAC_SHA256, key);
+
+    let mut orig: Vec<u8> = Vec::new();
+    // serial of the group currently being read, and whether its wheat was found
+    let mut current: Option<(u32, bool)> = None;
+    for p in packets.chunks_exact(WINNOW_PACKET_LEN) {
+        let serial = u32::from_be_bytes([p[0], p[1], p[2], p[3]]);
+        let value = p[4];
+        let tag = &p[5..];
+
+        match current {
+            Some((s, _)) if s == serial => {}
+            Some((_, false)) => return Err(format!("no authentic packet for serial {}", orig.len())),
+            _ => {
+                if serial as usize != orig.len() {
+                    return Err(format!("expected serial {}, found {serial}", orig.len()));
+                }
+                current = Some((serial, false));
+            }
+        }
+
+        if hmac::verify(&mac_key, &winnow_message(serial, value), tag).is_ok() {
+            if let Some((_, true)) = current {
+                return Err(format!("duplicate authentic packet for serial {serial}"));
+            }
+            orig.push(value);
+            current = Some((serial, true));
+        }
+    }
+    if let Some((s, false)) = current {
+        return Err(format!("no authentic packet for serial {s}"));
+    }
+    String::from_utf8(orig).map_err(|e| format!("not valid UTF-8: {e}"))
+}
+
+// --------------------- Streaming interleave/deinterleave (std only) ---------------------
+
+/// Incremental UTF-8 validator that carries an incomplete multibyte
+/// sequence (at most 3 bytes) across chunk boundaries.
+#[cfg(feature = "std")]
+#[derive(Clone, Default)]
+struct Utf8Validator {
+    pending: [u8; 4],
+    len: usize,
+}
+#[cfg(feature = "std")]
+impl Utf8Validator {
+    /// Expected sequence length from a leading byte (0 = not a valid leading byte).
+    fn char_width(b: u8) -> usize {
+        match b {
+            0x00..=0x7F => 1,
+            0xC2..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF4 => 4,
+            _ => 0,
+        }
+    }
+
+    fn invalid(e: core::str::Utf8Error) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, format!("not valid UTF-8: {e}"))
+    }
+
+    fn feed(&mut self, mut buf: &[u8]) -> io::Result<()> {
+        if self.len > 0 {
+            let need = Self::char_width(self.pending[0]);
+            let take = (need - self.len).min(buf.len());
+            self.pending[self.len..self.len + take].copy_from_slice(&buf[..take]);
+            self.len += take;
+            buf = &buf[take..];
+            match core::str::from_utf8(&self.pending[..self.len]) {
+                Ok(_) => self.len = 0,
+                Err(e) if e.error_len().is_some() => return Err(Self::invalid(e)),
+                // still incomplete: wait for more bytes
+                Err(_) => return Ok(()),
+            }
+        }
+        match core::str::from_utf8(buf) {
+            Ok(_) => Ok(()),
+            Err(e) if e.error_len().is_some() => Err(Self::invalid(e)),
+            Err(e) => {
+                let tail = &buf[e.valid_up_to()..];
+                self.pending[..tail.len()].copy_from_slice(tail);
+                self.len = tail.len();
+                Ok(())
+            }
+        }
+    }
+
+    fn finish(&self) -> io::Result<()> {
+        if self.len > 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not valid UTF-8: truncated multibyte sequence at end of stream",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Streaming counterpart of `interleave_with_random_bytes`.
+/// Bytes written are emitted to `inner` with one random byte between each
+/// original byte; the output is byte-for-byte the same layout as the
+/// in-memory function, regardless of how the input is split into writes.
+///
+/// Filler comes from `OsRng` by default; use [`InterleaveWriter::with_rng`]
+/// to supply your own generator (e.g. a seeded `HmacDrbg` for reproducible output).
+#[cfg(feature = "std")]
+pub struct InterleaveWriter<W: Write, R: RngCore = rand::rngs::OsRng> {
+    inner: W,
+    rng: R,
+    started: bool,
+    utf8: Option<Utf8Validator>,
+    scratch: Vec<u8>,
+}
+
+#[cfg(feature = "std")]
+impl<W: Write> InterleaveWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self::with_rng(inner, rand::rngs::OsRng)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Write, R: RngCore> InterleaveWriter<W, R> {
+    /// Filler bytes are drawn from `rng`, as in [`interleave_with_rng`]: the same
+    /// generator state yields the same output as the in-memory function.
+    pub fn with_rng(inner: W, rng: R) -> Self {
+        Self { inner, rng, started: false, utf8: None, scratch: Vec::new() }
+    }
+
+    /// Reject input that is not valid UTF-8 (multibyte characters may be split across writes).
+    pub fn with_utf8_validation(mut self) -> Self {
+        self.utf8 = Some(Utf8Validator::default());
+        self
+    }
+
+    /// Flush, check that the input did not end inside a UTF-8 character
+    /// (if validating), and return the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(v) = &self.utf8 {
+            v.finish()?;
+        }
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Write, R: RngCore> Write for InterleaveWriter<W, R> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if let Some(v) = &mut self.utf8 {
+            v.feed(buf)?;
+        }
+        self.scratch.clear();
+        for &b in buf {
+            if self.started {
+                self.scratch.push(self.rng.next_u32() as u8);
+            }
+            self.scratch.push(b);
+            self.started = true;
+        }
+        self.inner.write_all(&self.scratch)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Streaming counterpart of `deinterleave_original_bytes`.
+/// Reads interleaved bytes from `inner` and yields the original bytes
+/// (those at even stream offsets), keeping the offset parity across reads.
+#[cfg(feature = "std")]
+pub struct DeinterleaveReader<R: Read> {
+    inner: R,
+    offset: u64,
+    utf8: Option<Utf8Validator>,
+    scratch: Vec<u8>,
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> DeinterleaveReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self { inner, offset: 0, utf8: None, scratch: Vec::new() }
+    }
+
+    /// Fail with `InvalidData` if the recovered bytes are not valid UTF-8
+    /// (multibyte characters may be split across reads).
+    pub fn with_utf8_validation(mut self) -> Self {
+        self.utf8 = Some(Utf8Validator::default());
+        self
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> Read for DeinterleaveReader<R> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        if out.is_empty() {
+            return Ok(0);
+        }
+        // Enough interleaved bytes to fill `out`, whatever the current parity.
+        self.scratch.resize(out.len() * 2, 0);
+        loop {
+            let n = self.inner.read(&mut self.scratch)?;
+            if n == 0 {
+                if let Some(v) = &self.utf8 {
+                    v.finish()?;
+                }
+                return Ok(0);
+            }
+            let mut produced = 0;
+            for &b in &self.scratch[..n] {
+                if self.offset.is_multiple_of(2) {
+                    out[produced] = b;
+                    produced += 1;
+                }
+                self.offset += 1;
+            }
+            // A read that only returned chaff must not look like EOF.
+            if produced > 0 {
+                if let Some(v) = &mut self.utf8 {
+                    v.feed(&out[..produced])?;
+                }
+                return Ok(produced);
+            }
+        }
+    }
+}
@@ -12,74 +12,300 @@ impl KeyType for HkdfLen {
     }
 }
 
-use alloc::vec::Vec;
+use alloc::{string::{String, ToString}, vec::Vec};
+#[cfg(feature = "std")]
+use alloc::{boxed::Box, format};
 
+use rand::{CryptoRng, RngCore};
+
+use crate::secret::SecretBytes;
+
+#[cfg(feature = "std")]
+use crate::armor::{read_armor_file, write_armor_file, Armor, LABEL_KEY};
+#[cfg(feature = "std")]
 use crate::ascii_codec::{
     encode_bytes_ascii_wrapped,
     decode_bytes_ascii_wrapped,
 };
 
+/// Hash function for [`hkdf`] / [`hkdf_extended`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HkdfAlgorithm {
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HkdfAlgorithm {
+    fn ring(&self) -> hkdf::Algorithm {
+        match self {
+            HkdfAlgorithm::Sha256 => hkdf::HKDF_SHA256,
+            HkdfAlgorithm::Sha384 => hkdf::HKDF_SHA384,
+            HkdfAlgorithm::Sha512 => hkdf::HKDF_SHA512,
+        }
+    }
+
+    /// Hash output length in bytes (32, 48 or 64).
+    pub fn hash_len(&self) -> usize {
+        match self {
+            HkdfAlgorithm::Sha256 => 32,
+            HkdfAlgorithm::Sha384 => 48,
+            HkdfAlgorithm::Sha512 => 64,
+        }
+    }
+
+    /// Largest output RFC 5869 allows: 255 × hash length.
+    pub fn max_output_len(&self) -> usize {
+        255 * self.hash_len()
+    }
+}
+
+/// Domain separator for the segments of [`hkdf_extended`] past the RFC 5869
+/// limit. Segment `i` (from 0) is expanded with the info
+/// `u32be(info.len()) ‖ info ‖ "\0bitmasher:hkdf-ext:" ‖ u32be(i)`;
+/// the length prefix makes this injective, so segments of different calls
+/// (different `info`) or of the same call never share an HKDF info.
+const HKDF_EXT_LABEL: &[u8] = b"\0bitmasher:hkdf-ext:";
+
+/// RFC 5869 HKDF (extract + expand) with `out_len` bytes of output.
+/// `salt = None` uses an empty salt. Fails if `out_len` exceeds
+/// [`HkdfAlgorithm::max_output_len`]; see [`hkdf_extended`] for longer outputs.
+pub fn hkdf(
+    alg: HkdfAlgorithm,
+    ikm: &[u8],
+    salt: Option<&[u8]>,
+    info: &[u8],
+    out_len: usize,
+) -> Result<SecretBytes, &'static str> {
+    let prk = Salt::new(alg.ring(), salt.unwrap_or(&[])).extract(ikm);
+    let mut out = SecretBytes::zeroed(out_len);
+    hkdf_expand(&prk, &[info], out.expose_secret_mut())?;
+    Ok(out)
+}
+
+/// HKDF with a counter-mode extension for outputs beyond the RFC 5869 limit.
+///
+/// For `out_len <= M = max_output_len()` this is plain RFC 5869 and equals
+/// [`hkdf`]. Longer outputs are a sequence of segments of `M` bytes (the
+/// last one shorter), all expanded from the same PRK, where every segment,
+/// including the first, uses the length-prefixed info described at
+/// `HKDF_EXT_LABEL`:
+///
+/// - segment i = HKDF-Expand(PRK, u32be(len(info)) ‖ info ‖ "\0bitmasher:hkdf-ext:" ‖ u32be(i), M).
+///
+/// Extended outputs are prefixes of longer extended outputs. Fails only past
+/// 2^32 segments or a 4 GiB `info`.
+pub fn hkdf_extended(
+    alg: HkdfAlgorithm,
+    ikm: &[u8],
+    salt: Option<&[u8]>,
+    info: &[u8],
+    out_len: usize,
+) -> Result<SecretBytes, &'static str> {
+    let prk = Salt::new(alg.ring(), salt.unwrap_or(&[])).extract(ikm);
+    let mut out = SecretBytes::zeroed(out_len);
+    if out_len <= alg.max_output_len() {
+        hkdf_expand(&prk, &[info], out.expose_secret_mut())?;
+        return Ok(out);
+    }
+    let info_len = u32::try_from(info.len()).map_err(|_| "HKDF info too long")?.to_be_bytes();
+    for (i, segment) in out.expose_secret_mut().chunks_mut(alg.max_output_len()).enumerate() {
+        let counter = u32::try_from(i).map_err(|_| "HKDF output too long")?.to_be_bytes();
+        hkdf_expand(&prk, &[&info_len, info, HKDF_EXT_LABEL, &counter], segment)?;
+    }
+    Ok(out)
+}
+
+fn hkdf_expand(prk: &Prk, info: &[&[u8]], out: &mut [u8]) -> Result<(), &'static str> {
+    prk.expand(info, HkdfLen(out.len()))
+        .map_err(|_| "HKDF output too long")?
+        .fill(out)
+        .map_err(|_| "HKDF output too long")
+}
+
 /// Derive a cryptographically strong key of the same length as `input` using HKDF-SHA256.
 /// Deterministic: same `input` + `salt` + `info` => same key.
 /// - `salt` and `info` are optional context separation parameters (can be empty).
 /// - If you do not have a salt, pass `None` to use HKDF with an empty salt (still safe).
+/// - Inputs longer than 8160 bytes use the [`hkdf_extended`] counter mode.
 pub fn hkdf_key_same_len(
     input: &[u8],
     salt: Option<&[u8]>,
     info: &[u8],
-) -> Vec<u8> {
-    let salt_obj = Salt::new(hkdf::HKDF_SHA256, salt.unwrap_or(&[]));
-    let prk: Prk = salt_obj.extract(input);
-
-    let mut out = vec![0u8; input.len()];
-    let key_type = HkdfLen(input.len());
-
-    prk.expand(&[info], key_type)
-        .expect("HKDF expand failed")
-        .fill(&mut out)
-        .expect("HKDF fill failed");
-
-    out
+) -> SecretBytes {
+    hkdf_extended(HkdfAlgorithm::Sha256, input, salt, info, input.len())
+        .expect("HKDF output length is bounded by memory")
 }
 
 
 /// HKDF‑SHA512 key derivation of same length as input.
 /// Deterministic: (input, salt, info) → same key.
 /// Safe even with empty salt (RFC 5869 allows it).
+/// Inputs longer than 16320 bytes use the [`hkdf_extended`] counter mode.
 pub fn hkdf_sha512_same_len(
     input: &[u8],
     salt: Option<&[u8]>,
     info: &[u8],
-) -> Vec<u8> {
-    // Use HKDF-SHA512 instead of SHA256
-    let salt_obj = Salt::new(hkdf::HKDF_SHA512, salt.unwrap_or(&[]));
-    let prk: Prk = salt_obj.extract(input);
+) -> SecretBytes {
+    hkdf_extended(HkdfAlgorithm::Sha512, input, salt, info, input.len())
+        .expect("HKDF output length is bounded by memory")
+}
+
+
+// --------------------- Key hierarchy ---------------------
+
+/// Version tag of the hierarchy derivation; part of every HKDF call below.
+const HIERARCHY_LABEL: &[u8] = b"bitmasher:key-hierarchy:v1";
+
+/// Root (or subtree) of a key hierarchy: derives purpose-labeled subkeys
+/// along `/`-separated paths such as `tenant/a/pipeline/v2/rotation`.
+///
+/// Each path segment moves one level down a chain of HKDF calls:
+///
+/// ```text
+/// chain(root)      = HKDF(alg, ikm = secret, salt = LABEL, info = "root", HashLen)
+/// chain(p/segment) = HKDF(alg, ikm = chain(p), salt = LABEL,
+///                         info = "node" ‖ u32be(len(segment)) ‖ segment, HashLen)
+/// key(p, n)        = HKDF(alg, ikm = chain(p), salt = LABEL, info = "key" ‖ u32be(n), n)
+/// ```
+///
+/// Segments are length-prefixed and must be non-empty, so distinct paths
+/// never share an `info` string; the output length is bound in as well.
+/// A subtree (see [`MasterKey::subtree`]) can be handed out to derive keys
+/// below its path without revealing the keys above it.
+#[derive(Clone)]
+pub struct MasterKey {
+    alg: HkdfAlgorithm,
+    path: Vec<String>,
+    chain: SecretBytes,
+}
+
+/// A key derived by [`MasterKey::derive`], carrying the path it was derived for.
+#[derive(Clone)]
+pub struct DerivedKey {
+    alg: HkdfAlgorithm,
+    path: String,
+    key: SecretBytes,
+}
+
+impl MasterKey {
+    /// Hierarchy rooted at `secret`, using HKDF-SHA512.
+    pub fn new(secret: &[u8]) -> Self {
+        Self::with_algorithm(secret, HkdfAlgorithm::Sha512)
+    }
 
-    let mut out = vec![0u8; input.len()];
-    let key_type = HkdfLen(input.len());
+    pub fn with_algorithm(secret: &[u8], alg: HkdfAlgorithm) -> Self {
+        let chain = hkdf(alg, secret, Some(HIERARCHY_LABEL), b"root", alg.hash_len())
+            .expect("hash length is within the HKDF limit");
+        Self { alg, path: Vec::new(), chain }
+    }
+
+    /// Full path of this node (`""` for the root).
+    pub fn path(&self) -> String {
+        self.path.join("/")
+    }
 
-    prk.expand(&[info], key_type)
-        .expect("HKDF-SHA512 expand failed")
-        .fill(&mut out)
-        .expect("HKDF-SHA512 fill failed");
+    pub fn algorithm(&self) -> HkdfAlgorithm {
+        self.alg
+    }
+
+    /// The node at `path` below this one. Fails on an empty path or segment.
+    pub fn subtree(&self, path: &str) -> Result<MasterKey, &'static str> {
+        let mut node = self.clone();
+        for segment in split_key_path(path)? {
+            let mut info = Vec::with_capacity(8 + segment.len());
+            info.extend_from_slice(b"node");
+            info.extend_from_slice(&(segment.len() as u32).to_be_bytes());
+            info.extend_from_slice(segment.as_bytes());
+            node.chain = hkdf(node.alg, node.chain.expose_secret(), Some(HIERARCHY_LABEL), &info, node.alg.hash_len())?;
+            node.path.push(segment.to_string());
+        }
+        Ok(node)
+    }
 
-    out
+    /// Derive a `len`-byte key for `path` (relative to this node).
+    pub fn derive(&self, path: &str, len: usize) -> Result<DerivedKey, &'static str> {
+        let node = self.subtree(path)?;
+        let len_be = u32::try_from(len).map_err(|_| "key length too large")?.to_be_bytes();
+        let info = [b"key".as_slice(), &len_be].concat();
+        let key = hkdf_extended(node.alg, node.chain.expose_secret(), Some(HIERARCHY_LABEL), &info, len)?;
+        Ok(DerivedKey { alg: node.alg, path: node.path(), key })
+    }
 }
 
+impl core::fmt::Debug for MasterKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MasterKey")
+            .field("alg", &self.alg)
+            .field("path", &self.path())
+            .finish_non_exhaustive()
+    }
+}
+
+impl DerivedKey {
+    /// Full path from the hierarchy root, e.g. `tenant/a/pipeline/v2/rotation`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn algorithm(&self) -> HkdfAlgorithm {
+        self.alg
+    }
+
+    pub fn expose_secret(&self) -> &[u8] {
+        self.key.expose_secret()
+    }
+
+    pub fn into_secret(self) -> SecretBytes {
+        self.key
+    }
+
+    pub fn len(&self) -> usize {
+        self.key.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.key.is_empty()
+    }
+}
+
+impl core::fmt::Debug for DerivedKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DerivedKey")
+            .field("alg", &self.alg)
+            .field("path", &self.path)
+            .field("len", &self.key.len())
+            .finish_non_exhaustive()
+    }
+}
+
+fn split_key_path(path: &str) -> Result<Vec<&str>, &'static str> {
+    let segments: Vec<&str> = path.split('/').collect();
+    if segments.iter().any(|s| s.is_empty()) {
+        return Err("key path segments must be non-empty");
+    }
+    Ok(segments)
+}
+
+/// Generate a random key whose length equals `input.len()` from a
+/// caller-supplied CSPRNG (`no_std` friendly).
+/// Non-deterministic; the `input` content is ignored except for its length.
+pub fn csprng_key_same_len<R: RngCore + CryptoRng + ?Sized>(input: &[u8], rng: &mut R) -> SecretBytes {
+    let mut key = SecretBytes::zeroed(input.len());
+    rng.fill_bytes(key.expose_secret_mut());
+    key
+}
 
 /// Generate a cryptographically-strong random key whose length equals `input.len()`.
 /// Non-deterministic; the `input` content is ignored except for its length.
 ///
 /// Requires `std`. Uses the OS CSPRNG via `rand::rngs::OsRng`.
-pub fn csprng_key_same_len_std(input: &[u8]) -> Vec<u8> {
-    use rand::rngs::OsRng;
-    use rand::RngCore;
-
-    let mut key = vec![0u8; input.len()];
-    OsRng.fill_bytes(&mut key);
-    key
+#[cfg(feature = "std")]
+pub fn csprng_key_same_len_std(input: &[u8]) -> SecretBytes {
+    csprng_key_same_len(input, &mut rand::rngs::OsRng)
 }
 
+#[cfg(feature = "std")]
 pub fn export_key_ascii_file(
     path: &str,
     key: &[u8],
@@ -93,20 +319,22 @@ pub fn export_key_ascii_file(
     fs::write(path, wrapped)
 }
 
+#[cfg(feature = "std")]
 pub fn import_key_ascii_file(
     path: &str,
     prefix: &str,
     suffix: &str,
-) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+) -> Result<SecretBytes, Box<dyn std::error::Error>> {
     use std::fs;
 
     let content = fs::read_to_string(path)?;
     let decoded = decode_bytes_ascii_wrapped(&content, prefix, suffix)
         .map_err(|e| format!("decode error: {e}"))?;
 
-    Ok(decoded)
+    Ok(SecretBytes::new(decoded))
 }
 
+#[cfg(feature = "std")]
 pub fn derive_and_export_hkdf_sha512(
     input: &[u8],
     salt: Option<&[u8]>,
@@ -117,10 +345,11 @@ pub fn derive_and_export_hkdf_sha512(
     suffix: &str,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let key = crate::keygen::hkdf_sha512_same_len(input, salt, info);
-    export_key_ascii_file(path, &key, per_line, prefix, suffix)?;
+    export_key_ascii_file(path, key.expose_secret(), per_line, prefix, suffix)?;
     Ok(())
 }
 
+#[cfg(feature = "std")]
 pub fn import_and_use_key<F>(
     path: &str,
     prefix: &str,
@@ -131,6 +360,28 @@ where
     F: FnMut(&[u8]),
 {
     let key = import_key_ascii_file(path, prefix, suffix)?;
-    callback(&key);
+    callback(key.expose_secret());
     Ok(())
 }
+
+
+/// Export a plain key to an armored file (`-----BEGIN BITMASHER KEY-----`).
+/// `headers` are added after the default `Version` header.
+#[cfg(feature = "std")]
+pub fn export_key_armor_file(
+    path: &str,
+    key: &[u8],
+    headers: &[(&str, &str)],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut armor = Armor::new(LABEL_KEY, key);
+    for (k, v) in headers {
+        armor.set_header(k, v);
+    }
+    write_armor_file(path, &armor)
+}
+
+/// Import a plain key from an armored file (label and CRC-24 are verified).
+#[cfg(feature = "std")]
+pub fn import_key_armor_file(path: &str) -> Result<SecretBytes, Box<dyn std::error::Error>> {
+    Ok(SecretBytes::new(read_armor_file(path, LABEL_KEY)?.data))
+}
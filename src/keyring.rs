@@ -0,0 +1,340 @@
+//! keyring.rs — versioned keys with rotation
+//!
+//! A `KeyRing` holds numbered key versions. Exactly one version is `Active`
+//! and is used for new artifacts; older versions stay `DecryptOnly` so
+//! existing artifacts still open, until they are `Retired`.
+//!
+//! Artifacts are armored blocks that carry the version in a `Key-Version`
+//! header; decoding picks the key from that header. Besides the generic
+//! [`KeyRing::encode_armor`], v2 pipeline files can be protected with the
+//! active key (`pipeline_v2::export_pipeline_v2_armor_files_with_keyring`).
+//!
+//! `Retired` is final: a retired version can not be reactivated, so an
+//! artifact refused once stays refused.
+//!
+//! The ring itself is stored as a PPKE-protected `BITMASHER KEYRING` block.
+//! Binary layout (big-endian):
+//!
+//! ```text
+//! MAGIC "BMKR" | version u8 = 1 | next_id u32 | count u32
+//! count × ( id u32 | created u64 | status u8 | key_len u32 | key )
+//! ```
+//!
+//! Version ids are never reused, even after [`KeyRing::prune_retired`], so an
+//! old artifact can never be opened with a different key.
+
+extern crate alloc;
+
+use alloc::{format, string::{String, ToString}, vec::Vec};
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
+#[cfg(feature = "std")]
+use core::num::NonZeroU32;
+
+use rand::{CryptoRng, RngCore};
+
+use crate::armor::{Armor, HEADER_KEY_VERSION};
+use crate::secret::SecretBytes;
+#[cfg(feature = "std")]
+use crate::armor::{read_armor_file, write_armor_file, LABEL_DATA, LABEL_KEYRING};
+#[cfg(feature = "std")]
+use crate::interleave::{deinterleave_with_key, interleave_with_key};
+#[cfg(feature = "std")]
+use crate::ppke::{export_key_password_protected, import_key_password_protected};
+
+const MAGIC: &[u8; 4] = b"BMKR";
+const FORMAT_VERSION: u8 = 1;
+
+/// Lifecycle of a key version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyStatus {
+    /// Used for new artifacts (at most one per ring).
+    Active,
+    /// Only opens existing artifacts.
+    DecryptOnly,
+    /// Kept for the record; refuses all use.
+    Retired,
+}
+
+impl KeyStatus {
+    fn to_byte(self) -> u8 {
+        match self {
+            KeyStatus::Active => 1,
+            KeyStatus::DecryptOnly => 2,
+            KeyStatus::Retired => 3,
+        }
+    }
+
+    fn from_byte(b: u8) -> Option<Self> {
+        match b {
+            1 => Some(KeyStatus::Active),
+            2 => Some(KeyStatus::DecryptOnly),
+            3 => Some(KeyStatus::Retired),
+            _ => None,
+        }
+    }
+}
+
+/// One key version.
+#[derive(Clone)]
+pub struct KeyVersion {
+    pub id: u32,
+    /// Creation time, seconds since the Unix epoch (supplied by the caller).
+    pub created: u64,
+    pub status: KeyStatus,
+    key: SecretBytes,
+}
+
+impl KeyVersion {
+    pub fn key(&self) -> &SecretBytes {
+        &self.key
+    }
+}
+
+impl core::fmt::Debug for KeyVersion {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("KeyVersion")
+            .field("id", &self.id)
+            .field("created", &self.created)
+            .field("status", &self.status)
+            .field("key", &self.key)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Versioned keys; see the module docs.
+#[derive(Clone, Debug)]
+pub struct KeyRing {
+    versions: Vec<KeyVersion>,
+    next_id: u32,
+}
+
+impl Default for KeyRing {
+    fn default() -> Self {
+        Self { versions: Vec::new(), next_id: 1 }
+    }
+}
+
+impl KeyRing {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `key` as a new version and make it active; the previously active
+    /// version becomes `DecryptOnly`. Returns the new version id.
+    pub fn add(&mut self, key: &[u8], created: u64) -> Result<u32, String> {
+        if key.is_empty() {
+            return Err("key must not be empty".into());
+        }
+        let id = self.next_id;
+        self.next_id = id.checked_add(1).ok_or("key version ids exhausted")?;
+        self.demote_active();
+        self.versions.push(KeyVersion { id, created, status: KeyStatus::Active, key: key.into() });
+        Ok(id)
+    }
+
+    /// Rotate: add a fresh random `len`-byte key from `rng` as the active version.
+    pub fn generate<R: RngCore + CryptoRng + ?Sized>(
+        &mut self,
+        len: usize,
+        created: u64,
+        rng: &mut R,
+    ) -> Result<u32, String> {
+        let mut key = SecretBytes::zeroed(len);
+        rng.try_fill_bytes(key.expose_secret_mut()).map_err(|_| "rng key".to_string())?;
+        self.add(key.expose_secret(), created)
+    }
+
+    /// Change the status of version `id`. Activating a version demotes the
+    /// current active one to `DecryptOnly`. A `Retired` version stays retired.
+    pub fn set_status(&mut self, id: u32, status: KeyStatus) -> Result<(), String> {
+        let i = self.position(id)?;
+        if self.versions[i].status == KeyStatus::Retired && status != KeyStatus::Retired {
+            return Err(format!("key version {id} is retired"));
+        }
+        if status == KeyStatus::Active {
+            self.demote_active();
+        }
+        self.versions[i].status = status;
+        Ok(())
+    }
+
+    /// Drop the key material of all `Retired` versions.
+    pub fn prune_retired(&mut self) {
+        self.versions.retain(|v| v.status != KeyStatus::Retired);
+    }
+
+    pub fn versions(&self) -> &[KeyVersion] {
+        &self.versions
+    }
+
+    pub fn get(&self, id: u32) -> Option<&KeyVersion> {
+        self.versions.iter().find(|v| v.id == id)
+    }
+
+    /// The version used for new artifacts.
+    pub fn active(&self) -> Result<&KeyVersion, String> {
+        self.versions
+            .iter()
+            .find(|v| v.status == KeyStatus::Active)
+            .ok_or_else(|| "keyring has no active key".to_string())
+    }
+
+    /// The version `id` for opening an artifact (`Active` or `DecryptOnly`).
+    pub fn for_decode(&self, id: u32) -> Result<&KeyVersion, String> {
+        let v = self.get(id).ok_or_else(|| format!("key version {id} not in keyring"))?;
+        if v.status == KeyStatus::Retired {
+            return Err(format!("key version {id} is retired"));
+        }
+        Ok(v)
+    }
+
+    /// Run `encode` with the active key and wrap its output in an armored
+    /// block labeled `label`, tagged with the key version.
+    pub fn encode_armor<F>(&self, label: &str, encode: F) -> Result<Armor, String>
+    where
+        F: FnOnce(&[u8]) -> Result<Vec<u8>, String>,
+    {
+        let active = self.active()?;
+        let data = encode(active.key.expose_secret())?;
+        Ok(Armor::new(label, &data).with_header(HEADER_KEY_VERSION, &active.id.to_string()))
+    }
+
+    /// The version named in the block's `Key-Version` header, for decoding.
+    pub fn for_armor(&self, armor: &Armor) -> Result<&KeyVersion, String> {
+        let id = armor
+            .header(HEADER_KEY_VERSION)
+            .ok_or_else(|| format!("armor has no {HEADER_KEY_VERSION} header"))?;
+        let id: u32 = id.parse().map_err(|_| format!("invalid {HEADER_KEY_VERSION} header '{id}'"))?;
+        self.for_decode(id)
+    }
+
+    /// Run `decode` on the block's data with the key version named in its
+    /// `Key-Version` header.
+    pub fn decode_armor<F, T>(&self, armor: &Armor, decode: F) -> Result<T, String>
+    where
+        F: FnOnce(&[u8], &[u8]) -> Result<T, String>,
+    {
+        decode(self.for_armor(armor)?.key.expose_secret(), &armor.data)
+    }
+
+    /// Serialize (unencrypted; see [`KeyRing::save_armor_file`]).
+    pub fn to_bytes(&self) -> SecretBytes {
+        // sized up front: a reallocation would leave key bytes behind
+        let len = 13 + self.versions.iter().map(|v| 17 + v.key.len()).sum::<usize>();
+        let mut out = Vec::with_capacity(len);
+        out.extend_from_slice(MAGIC);
+        out.push(FORMAT_VERSION);
+        out.extend_from_slice(&self.next_id.to_be_bytes());
+        out.extend_from_slice(&(self.versions.len() as u32).to_be_bytes());
+        for v in &self.versions {
+            out.extend_from_slice(&v.id.to_be_bytes());
+            out.extend_from_slice(&v.created.to_be_bytes());
+            out.push(v.status.to_byte());
+            out.extend_from_slice(&(v.key.len() as u32).to_be_bytes());
+            out.extend_from_slice(v.key.expose_secret());
+        }
+        SecretBytes::new(out)
+    }
+
+    /// Inverse of [`KeyRing::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let mut r = Reader(bytes);
+        if r.take(4)? != MAGIC {
+            return Err("not a keyring (bad magic)".into());
+        }
+        let version = r.take(1)?[0];
+        if version != FORMAT_VERSION {
+            return Err(format!("unsupported keyring format version {version}"));
+        }
+        let next_id = r.u32()?;
+        let count = r.u32()?;
+        let mut ring = KeyRing { versions: Vec::new(), next_id };
+        for _ in 0..count {
+            let id = r.u32()?;
+            let created = u64::from_be_bytes(r.take(8)?.try_into().expect("8 bytes"));
+            let status = KeyStatus::from_byte(r.take(1)?[0]).ok_or("invalid key status")?;
+            let len = r.u32()? as usize;
+            let key = SecretBytes::from(r.take(len)?);
+            if id == 0 || id >= next_id || ring.get(id).is_some() {
+                return Err(format!("invalid key version id {id}"));
+            }
+            ring.versions.push(KeyVersion { id, created, status, key });
+        }
+        if !r.0.is_empty() {
+            return Err("trailing bytes after keyring".into());
+        }
+        if ring.versions.iter().filter(|v| v.status == KeyStatus::Active).count() > 1 {
+            return Err("keyring has more than one active key".into());
+        }
+        Ok(ring)
+    }
+
+    fn position(&self, id: u32) -> Result<usize, String> {
+        self.versions
+            .iter()
+            .position(|v| v.id == id)
+            .ok_or_else(|| format!("key version {id} not in keyring"))
+    }
+
+    fn demote_active(&mut self) {
+        for v in &mut self.versions {
+            if v.status == KeyStatus::Active {
+                v.status = KeyStatus::DecryptOnly;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl KeyRing {
+    /// Keyed interleave (see `interleave::interleave_with_key`) with the
+    /// active key, as a `BITMASHER DATA` block with a `Key-Version` header.
+    pub fn interleave_to_armor(&self, input: &str) -> Result<Armor, String> {
+        self.encode_armor(LABEL_DATA, |key| Ok(interleave_with_key(input, key)))
+    }
+
+    /// Inverse of [`KeyRing::interleave_to_armor`], using the key version
+    /// recorded in the block.
+    pub fn deinterleave_from_armor(&self, armor: &Armor) -> Result<String, String> {
+        armor.expect_label(LABEL_DATA)?;
+        self.decode_armor(armor, |key, data| deinterleave_with_key(data, key))
+    }
+
+    /// Save as a PPKE-protected `BITMASHER KEYRING` armored file.
+    pub fn save_armor_file(
+        &self,
+        path: &str,
+        password: &SecretBytes,
+        iterations: Option<NonZeroU32>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let blob = export_key_password_protected(self.to_bytes().expose_secret(), password, iterations, 16)?;
+        write_armor_file(path, &Armor::new(LABEL_KEYRING, &blob))
+    }
+
+    /// Load a keyring saved by [`KeyRing::save_armor_file`].
+    pub fn load_armor_file(path: &str, password: &SecretBytes) -> Result<Self, Box<dyn std::error::Error>> {
+        let armor = read_armor_file(path, LABEL_KEYRING)?;
+        let bytes = import_key_password_protected(&armor.data, password)
+            .map_err(|e| format!("decrypt: {e}"))?;
+        Ok(Self::from_bytes(bytes.expose_secret())?)
+    }
+}
+
+/// Minimal cursor for `from_bytes`.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        if self.0.len() < n {
+            return Err("keyring data truncated".into());
+        }
+        let (head, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().expect("4 bytes")))
+    }
+}
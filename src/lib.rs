@@ -1,16 +1,68 @@
+//! Cargo features:
+//! - `alloc`: codecs, armor, pipelines and PPKE in memory. Functions that need
+//!   randomness take a caller-supplied `RngCore` (`*_with_rng`, `*_rng`).
+//! - `std` (default, implies `alloc`): file helpers, `io` streaming adapters,
+//!   and the functions that draw from the OS CSPRNG or system time.
+//!
+//! Without either feature only `BitArray` is available.
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+#[cfg(feature = "alloc")]
+extern crate alloc;
 extern crate log;
 
+#[cfg(feature = "alloc")]
 pub mod ascii_codec;
+#[cfg(feature = "alloc")]
+pub mod armor;
+#[cfg(feature = "alloc")]
+pub mod fec;
+#[cfg(feature = "alloc")]
+pub mod paper;
+#[cfg(feature = "alloc")]
+pub mod stego;
+#[cfg(feature = "alloc")]
+pub mod symbols;
+#[cfg(feature = "alloc")]
 pub mod utf8util;
+#[cfg(feature = "alloc")]
+pub mod wordlist;
+#[cfg(feature = "alloc")]
 pub mod interleave;
+#[cfg(feature = "alloc")]
+pub mod drbg;
+#[cfg(feature = "alloc")]
+pub mod fingerprint;
+#[cfg(feature = "alloc")]
 pub mod keygen;
+#[cfg(feature = "alloc")]
+pub mod keyring;
+#[cfg(feature = "alloc")]
+pub mod secret;
+#[cfg(feature = "alloc")]
+pub mod shamir;
+#[cfg(feature = "alloc")]
+pub mod signing;
+#[cfg(feature = "alloc")]
 pub mod ppke;
+#[cfg(feature = "alloc")]
+pub mod recipient;
+#[cfg(feature = "alloc")]
 pub mod pipeline;
+#[cfg(feature = "alloc")]
 pub mod pipeline_v2;
+#[cfg(feature = "alloc")]
 pub mod pipeline_inverse;
+#[cfg(feature = "std")]
 pub mod pipeline_decode;
+#[cfg(feature = "alloc")]
 pub mod pipeline_decode_v2;
 
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String, vec, vec::Vec};
+
 /// Fixed-size bit array over `N` bytes, with effective `bit_len` bits in use.
 /// Bits are indexed from 0..bit_len-1, bit 0 is the LSB of data[0].
 #[derive(Clone)]
@@ -79,10 +131,8 @@ impl<const N: usize> BitArray<N> {
         }
 
         // copy back exactly participating bytes
-        let bytes_used = (bit_len + 7) / 8;
-        for i in 0..bytes_used {
-            self.data[i] = out[i];
-        }
+        let bytes_used = bit_len.div_ceil(8);
+        self.data[..bytes_used].copy_from_slice(&out[..bytes_used]);
 
         // zero unused bytes & mask tail bits
         self.mask_tail();
@@ -103,7 +153,7 @@ impl<const N: usize> BitArray<N> {
             return;
         }
 
-        let byte_count = (self.bit_len + 7) / 8;
+        let byte_count = self.bit_len.div_ceil(8);
 
         // Zero bytes not participating
         for i in byte_count..N { self.data[i] = 0; }
@@ -117,3 +167,115 @@ impl<const N: usize> BitArray<N> {
         }
     }
 }
+
+/// Heap-allocated bit vector with exact `bit_len`, for outputs whose size is not
+/// known at compile time. Same bit order as `BitArray`: bit 0 is the LSB of byte 0.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BitVec {
+    data: Vec<u8>,
+    bit_len: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl BitVec {
+    /// Create an empty BitVec.
+    pub fn new() -> Self { Self::default() }
+
+    /// Create an empty BitVec with room for `bits` bits.
+    pub fn with_capacity(bits: usize) -> Self {
+        Self { data: Vec::with_capacity(bits.div_ceil(8)), bit_len: 0 }
+    }
+
+    /// Create a BitVec from bytes and an effective bit length (<= bytes.len()*8).
+    /// Bytes beyond `bit_len` are dropped and unused tail bits are masked to 0.
+    pub fn from_bytes(bytes: &[u8], bit_len: usize) -> Self {
+        assert!(bit_len <= bytes.len() * 8, "bit_len exceeds storage");
+        let mut s = Self { data: bytes[..bit_len.div_ceil(8)].to_vec(), bit_len };
+        let rem_bits = bit_len % 8;
+        if rem_bits != 0 {
+            let last = s.data.len() - 1;
+            s.data[last] &= ((1u16 << rem_bits) - 1) as u8;
+        }
+        s
+    }
+
+    /// Returns the underlying bytes (`bit_len.div_ceil(8)` of them).
+    pub fn as_bytes(&self) -> &[u8] { &self.data }
+
+    /// Consumes the BitVec and returns the underlying bytes.
+    pub fn into_bytes(self) -> Vec<u8> { self.data }
+
+    /// Total number of bits in use.
+    pub fn bit_len(&self) -> usize { self.bit_len }
+
+    /// True if no bits are in use.
+    pub fn is_empty(&self) -> bool { self.bit_len == 0 }
+
+    /// Append one bit.
+    pub fn push(&mut self, val: bool) {
+        let off = self.bit_len % 8;
+        if off == 0 { self.data.push(0); }
+        if val {
+            let last = self.data.len() - 1;
+            self.data[last] |= 1 << off;
+        }
+        self.bit_len += 1;
+    }
+
+    /// Get bit i (0..bit_len-1). Panics on out-of-range.
+    pub fn get_bit(&self, i: usize) -> bool {
+        assert!(i < self.bit_len, "bit index out of range");
+        ((self.data[i / 8] >> (i % 8)) & 1) != 0
+    }
+
+    /// Set bit i to `val`. Panics on out-of-range.
+    pub fn set_bit(&mut self, i: usize, val: bool) {
+        assert!(i < self.bit_len, "bit index out of range");
+        if val { self.data[i / 8] |= 1 << (i % 8); }
+        else   { self.data[i / 8] &= !(1 << (i % 8)); }
+    }
+
+    /// Rotate left by `k` bits across all `bit_len` bits (contiguous bit ring).
+    pub fn rotate_left(&mut self, k: usize) {
+        let bit_len = self.bit_len;
+        if bit_len == 0 { return; }
+        let k = k % bit_len;
+        if k == 0 { return; }
+
+        let mut out = vec![0u8; self.data.len()];
+        for i in 0..bit_len {
+            if self.get_bit(i) {
+                let j = (i + k) % bit_len;
+                out[j / 8] |= 1 << (j % 8);
+            }
+        }
+        self.data = out;
+    }
+
+    pub fn rotate_right(&mut self, k: usize) {
+        let bit_len = self.bit_len;
+        if bit_len == 0 { return; }
+        let k = k % bit_len;
+        if k == 0 { return; }
+        self.rotate_left(bit_len - k);
+    }
+
+    /// Copy into a fixed-size `BitArray<N>` with the same `bit_len`.
+    /// Fails if the bits do not fit in `N` bytes.
+    pub fn to_bitarray<const N: usize>(&self) -> Result<BitArray<N>, String> {
+        if self.data.len() > N {
+            return Err(format!("BitVec needs {} bytes, BitArray has {}", self.data.len(), N));
+        }
+        let mut storage = [0u8; N];
+        storage[..self.data.len()].copy_from_slice(&self.data);
+        Ok(BitArray::new(storage, self.bit_len))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> From<&BitArray<N>> for BitVec {
+    fn from(bits: &BitArray<N>) -> Self {
+        BitVec::from_bytes(bits.as_bytes(), bits.bit_len())
+    }
+}
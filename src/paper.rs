@@ -0,0 +1,136 @@
+//! paper.rs — printable paper-backup format (paperkey style)
+//!
+//! Meant for PPKE blobs that get printed and typed back in by hand:
+//!
+//! ```text
+//! # bitmasher paper backup v1
+//! # <line>: <hex bytes> <CRC-24 of line number + bytes>
+//!   1: 42 4D 4B 31 01 01 00 09 27 C0 10 0C 00 00 00 20 5E0A21
+//!   2: ...
+//! total: 76 3FA2C1
+//! ```
+//!
+//! - Each data line carries its 1-based number and a CRC-24 over
+//!   (line number as u32 BE || line bytes), so a typo, a swapped or a
+//!   skipped line is pinned to that line.
+//! - The `total:` line carries the byte length and the CRC-24 of the whole blob.
+//! - On decode, `#` comments and blank lines are ignored, hex is
+//!   case-insensitive and the grouping of hex digits within a line is free.
+
+extern crate alloc;
+
+use alloc::{format, string::{String, ToString}, vec::Vec};
+
+use crate::armor::crc24;
+use crate::ascii_codec::{DecodeError, DecodeErrorKind};
+
+const HEADER: &str = "# bitmasher paper backup v1\n# <line>: <hex bytes> <CRC-24 of line number + bytes>\n";
+const TOTAL: &str = "total:";
+
+fn line_crc(line_no: u32, bytes: &[u8]) -> u32 {
+    let mut buf = Vec::with_capacity(4 + bytes.len());
+    buf.extend_from_slice(&line_no.to_be_bytes());
+    buf.extend_from_slice(bytes);
+    crc24(&buf)
+}
+
+fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(10 + c - b'a'),
+        b'A'..=b'F' => Some(10 + c - b'A'),
+        _ => None,
+    }
+}
+
+fn parse_hex(s: &str) -> Option<Vec<u8>> {
+    let digits: Vec<u8> = s.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    if !digits.len().is_multiple_of(2) {
+        return None;
+    }
+    digits
+        .chunks(2)
+        .map(|p| Some((hex_value(p[0])? << 4) | hex_value(p[1])?))
+        .collect()
+}
+
+fn parse_crc(s: &str) -> Option<u32> {
+    if s.len() != 6 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(s, 16).ok()
+}
+
+/// Render `data` as a paper backup with `per_line` bytes per line.
+pub fn encode_paper(data: &[u8], per_line: usize) -> String {
+    assert!(per_line >= 1, "per_line must be >= 1");
+
+    let lines = data.len().div_ceil(per_line);
+    let width = lines.to_string().len().max(3);
+    let mut out = String::from(HEADER);
+    for (i, chunk) in data.chunks(per_line).enumerate() {
+        let n = i as u32 + 1;
+        out.push_str(&format!("{n:>width$}:"));
+        for b in chunk {
+            out.push_str(&format!(" {b:02X}"));
+        }
+        out.push_str(&format!(" {:06X}\n", line_crc(n, chunk)));
+    }
+    out.push_str(&format!("{TOTAL} {} {:06X}\n", data.len(), crc24(data)));
+    out
+}
+
+/// Parse a paper backup back into bytes.
+/// Errors name the text line (and column) that failed, so the user knows
+/// exactly which printed line to re-type. `token` is the index of the
+/// first blob byte on that line.
+pub fn decode_paper(text: &str) -> Result<Vec<u8>, DecodeError> {
+    let mut out = Vec::new();
+    let mut expected_line: u32 = 1;
+    let mut line_start = 0;
+
+    for raw in text.split('\n') {
+        let start = line_start;
+        line_start += raw.len() + 1;
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let at = start + (raw.len() - raw.trim_start().len());
+        let err = |kind, off: usize| DecodeError::at(kind, text, at + off, out.len());
+
+        let (head, rest) = line.split_once(':').ok_or_else(|| err(DecodeErrorKind::LineNumber, 0))?;
+        let rest_off = head.len() + 1;
+
+        if head.trim().eq_ignore_ascii_case(TOTAL.trim_end_matches(':')) {
+            let mut parts = rest.split_whitespace();
+            let len = parts.next().and_then(|p| p.parse::<usize>().ok());
+            let crc = parts.next().and_then(parse_crc);
+            if len != Some(out.len()) || crc != Some(crc24(&out)) || parts.next().is_some() {
+                return Err(err(DecodeErrorKind::Checksum, rest_off));
+            }
+            return Ok(out);
+        }
+
+        if head.trim().parse::<u32>().ok() != Some(expected_line) {
+            return Err(err(DecodeErrorKind::LineNumber, 0));
+        }
+
+        let rest_trim = rest.trim_end();
+        let (hex, crc_str) = rest_trim
+            .rsplit_once(char::is_whitespace)
+            .ok_or_else(|| err(DecodeErrorKind::LineChecksum, rest_off))?;
+        let crc_off = rest_off + rest_trim.len() - crc_str.len();
+        let crc = parse_crc(crc_str).ok_or_else(|| err(DecodeErrorKind::LineChecksum, crc_off))?;
+        let bytes = parse_hex(hex).ok_or_else(|| err(DecodeErrorKind::InvalidHex, rest_off))?;
+        if line_crc(expected_line, &bytes) != crc {
+            return Err(err(DecodeErrorKind::LineChecksum, 0));
+        }
+
+        out.extend_from_slice(&bytes);
+        expected_line += 1;
+    }
+
+    // no total line: the backup was cut short
+    Err(DecodeError::at(DecodeErrorKind::Checksum, text, text.len(), out.len()))
+}
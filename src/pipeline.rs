@@ -1,10 +1,15 @@
 extern crate alloc;
 
-use alloc::{vec::Vec, string::String};
+use alloc::{vec::Vec, string::{String, ToString}};
 
+use rand::RngCore;
+
+#[cfg(feature = "std")]
 use crate::interleave::interleave_with_random_bytes;
+use crate::interleave::interleave_with_rng;
 use crate::BitArray;
 use crate::keygen::hkdf_sha512_same_len;
+use crate::secret::SecretBytes;
 use crate::utf8util::{utf8_bytes_to_ord_usize};
 
 /// Result object containing all pipeline outputs.
@@ -12,7 +17,7 @@ pub struct PipelineResult<const N: usize> {
     pub original: String,
     pub interleaved: Vec<u8>,
     pub bitarray_final: BitArray<N>,
-    pub hkdf_key: Vec<u8>,
+    pub hkdf_key: SecretBytes,
     pub ordinals: Vec<usize>,
 }
 
@@ -23,16 +28,39 @@ pub struct PipelineResult<const N: usize> {
 /// 4. HKDF-SHA512 key = same length as BitArray bytes
 /// 5. ordinals = utf8_bytes_to_ord_usize(input.as_bytes())
 /// 6. rotate BitArray left/right based on ordinals:
-///        even ordinal → rotate_left(ord)
-///        odd ordinal  → rotate_right(ord)
+///    even ordinal → rotate_left(ord)
+///    odd ordinal  → rotate_right(ord)
 ///
 /// `N` = storage size of the BitArray.
 ///
 /// If input is longer than N bytes, it is truncated.
+///
+/// Requires `std` (interleave filler seeded from system time); see
+/// [`process_str_pipeline_with_rng`].
+#[cfg(feature = "std")]
 pub fn process_str_pipeline<const N: usize>(
     input: &str,
     salt: Option<&[u8]>,
     info: &[u8],
+) -> PipelineResult<N> {
+    process_interleaved::<N>(input, interleave_with_random_bytes(input), salt, info)
+}
+
+/// Same as [`process_str_pipeline`], with interleave filler drawn from `rng`.
+pub fn process_str_pipeline_with_rng<const N: usize>(
+    input: &str,
+    salt: Option<&[u8]>,
+    info: &[u8],
+    rng: &mut (impl RngCore + ?Sized),
+) -> PipelineResult<N> {
+    process_interleaved::<N>(input, interleave_with_rng(input, rng), salt, info)
+}
+
+fn process_interleaved<const N: usize>(
+    input: &str,
+    inter: Vec<u8>,
+    salt: Option<&[u8]>,
+    info: &[u8],
 ) -> PipelineResult<N> {
     // ------------------------------------------------------------
     // Step 1: UTF-8 bytes
@@ -40,9 +68,8 @@ pub fn process_str_pipeline<const N: usize>(
     let utf8 = input.as_bytes();
 
     // ------------------------------------------------------------
-    // Step 2: Interleave
+    // Step 2: Interleave (done by the caller)
     // ------------------------------------------------------------
-    let inter = interleave_with_random_bytes(input);
 
     //  Fit interleaved bytes into BitArray storage
     let mut storage = [0u8; N];
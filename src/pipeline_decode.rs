@@ -1,12 +1,13 @@
 extern crate alloc;
 
-use alloc::{string::String};
+use alloc::{format, string::String};
 
 use crate::ascii_codec::decode_bytes_ascii_wrapped;
 use crate::interleave::deinterleave_original_bytes;
 use crate::ppke::{
     import_key_password_protected_ascii_file,
 };
+use crate::secret::SecretBytes;
 use crate::utf8util::utf8_bytes_to_ord_usize;
 use crate::BitArray;
 
@@ -26,7 +27,7 @@ use crate::BitArray;
 pub fn decode_pipeline_from_files<const N: usize>(
     wrapped_key_file: &str,
     wrapped_data_file: &str,
-    password: &[u8],
+    password: &SecretBytes,
     prefix: &str,
     suffix: &str,
     _salt: Option<&[u8]>,
@@ -84,7 +85,7 @@ pub fn decode_pipeline_from_files<const N: usize>(
     //
     // If you want a different derivation rule, I can update it.
     //
-    let ordinals = utf8_bytes_to_ord_usize(&key);
+    let ordinals = utf8_bytes_to_ord_usize(key.expose_secret());
 
     // Apply reverse rotations directly:
     for &o in ordinals.iter().rev() {
@@ -1,12 +1,31 @@
 extern crate alloc;
 
-use alloc::string::String;
+use alloc::{format, string::String};
+#[cfg(feature = "std")]
+use alloc::{string::ToString, vec::Vec};
 
+#[cfg(feature = "std")]
+use crate::armor::{
+    read_armor_file, read_armor_file_all, Armor, HEADER_KEY_ID, HEADER_KEY_VERSION, LABEL_DATA, LABEL_PROTECTED_KEY,
+};
+#[cfg(feature = "std")]
 use crate::ascii_codec::decode_bytes_ascii_wrapped;
 use crate::BitArray;
 use crate::interleave::deinterleave_original_bytes;
 use crate::keygen::{hkdf_sha512_same_len};
-use crate::ppke::{import_key_password_protected_ascii_file};
+use crate::secret::ct_eq;
+#[cfg(feature = "std")]
+use crate::fingerprint::Fingerprint;
+#[cfg(feature = "std")]
+use crate::keyring::KeyRing;
+#[cfg(feature = "std")]
+use crate::symbols::{decode_symbols, SymbolSet};
+#[cfg(feature = "std")]
+use crate::ppke::{import_key_password_protected, protected_key_len};
+#[cfg(feature = "std")]
+use crate::secret::SecretBytes;
+#[cfg(feature = "std")]
+use crate::signing::{verify_armor, Ed25519PublicKey};
 
 #[inline]
 fn ordinals_from_key_bytes(key: &[u8]) -> alloc::vec::Vec<usize> {
@@ -19,30 +38,206 @@ fn ordinals_from_key_bytes(key: &[u8]) -> alloc::vec::Vec<usize> {
 /// - password: PPKE password
 /// - prefix/suffix: ASCII armor boundaries
 /// - salt/info: HKDF params used in forward (for re-derivation check)
+///
+/// Files from `export_pipeline_v2_ascii_files` carry a `Key-Id` line, so a
+/// mismatched pair fails fast with "wrong key file"; files without one still decode.
+#[cfg(feature = "std")]
 pub fn decode_pipeline_v2_from_files<const N: usize>(
     wrapped_key_file: &str,
     wrapped_data_file: &str,
-    password: &[u8],
+    password: &SecretBytes,
     prefix: &str,
     suffix: &str,
     salt: Option<&[u8]>,
     info: &[u8],
 ) -> Result<String, String> {
-    use std::fs;
+    // (1) Load **ROTATED** bytes
+    let (needed, rotated) = read_ascii_wrapped_file(wrapped_data_file, prefix, suffix)
+        .map_err(|e| format!("read data: {e}"))?;
 
-    // (1) Decrypt HKDF key (length must equal N)
-    let hkdf_key = import_key_password_protected_ascii_file(
-        wrapped_key_file, password, prefix, suffix
-    ).map_err(|e| format!("key import: {e}"))?;
-    if hkdf_key.len() != N {
-        return Err(format!("hkdf_key length {} != N {}", hkdf_key.len(), N));
+    // (2) Decrypt HKDF key; its length (len == N) and the `Key-Id` lines
+    //     written by `export_pipeline_v2_ascii_files` are checked before PBKDF2.
+    let (have, blob) = read_ascii_wrapped_file(wrapped_key_file, prefix, suffix)
+        .map_err(|e| format!("key import: {e}"))?;
+    let hkdf_key = open_key_for_id::<N>(&blob, needed.as_deref(), have.as_deref(), password)?;
+
+    decode_pipeline_v2_bytes::<N>(hkdf_key.expose_secret(), &rotated, salt, info)
+}
+
+/// Read an ASCII-wrapped file, with the optional `Key-Id: ...` line that
+/// `export_pipeline_v2_ascii_files` puts right after `prefix`.
+#[cfg(feature = "std")]
+fn read_ascii_wrapped_file(path: &str, prefix: &str, suffix: &str) -> Result<(Option<String>, Vec<u8>), String> {
+    let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let tag = format!("{HEADER_KEY_ID}: ");
+    let id_line = text
+        .strip_prefix(prefix)
+        .filter(|rest| rest.starts_with(&tag))
+        .and_then(|rest| rest.split_inclusive('\n').next());
+    let bytes = match id_line {
+        Some(line) => decode_bytes_ascii_wrapped(&text, &format!("{prefix}{line}"), suffix),
+        None => decode_bytes_ascii_wrapped(&text, prefix, suffix),
+    }
+    .map_err(|e| format!("decode armor: {e}"))?;
+    Ok((id_line.map(|line| line[tag.len()..].trim().to_string()), bytes))
+}
+
+/// v2 decode pipeline over armored files (see `armor`):
+/// - wrapped_key_file: `BITMASHER PROTECTED KEY` block (contains HKDF key, len == N)
+/// - wrapped_data_file: `BITMASHER DATA` block (contains **ROTATED** bytes)
+///
+/// Blocks are picked by label, so both arguments may name the same file.
+/// Labels, CRC-24 checksums and the `Key-Id` binding (see
+/// `export_pipeline_v2_armor_files`) are verified before the key is decrypted,
+/// so a mismatched pair fails fast with "wrong key file".
+#[cfg(feature = "std")]
+pub fn decode_pipeline_v2_from_armor_files<const N: usize>(
+    wrapped_key_file: &str,
+    wrapped_data_file: &str,
+    password: &SecretBytes,
+    salt: Option<&[u8]>,
+    info: &[u8],
+) -> Result<String, String> {
+    // Check both armors first: it is cheap, key import runs PBKDF2.
+    let data = read_armor_file(wrapped_data_file, LABEL_DATA)
+        .map_err(|e| format!("read data: {e}"))?;
+    let key = read_armor_file(wrapped_key_file, LABEL_PROTECTED_KEY)
+        .map_err(|e| format!("key import: {e}"))?;
+
+    let hkdf_key = open_bound_key::<N>(&key, &data, password)?;
+    decode_pipeline_v2_bytes::<N>(hkdf_key.expose_secret(), &data.data, salt, info)
+}
+
+/// [`decode_pipeline_v2_from_armor_files`] for files written by
+/// `export_pipeline_v2_armor_files_with_keyring`: the key is the ring
+/// version named in the data block's `Key-Version` header, so files from
+/// before a rotation still open while that version is not `Retired`.
+#[cfg(feature = "std")]
+pub fn decode_pipeline_v2_from_keyring_armor_files<const N: usize>(
+    wrapped_key_file: &str,
+    wrapped_data_file: &str,
+    ring: &KeyRing,
+    salt: Option<&[u8]>,
+    info: &[u8],
+) -> Result<String, String> {
+    let data = read_armor_file(wrapped_data_file, LABEL_DATA)
+        .map_err(|e| format!("read data: {e}"))?;
+    let key = read_armor_file(wrapped_key_file, LABEL_PROTECTED_KEY)
+        .map_err(|e| format!("key import: {e}"))?;
+    if let (Some(needed), Some(have)) = (data.header(HEADER_KEY_VERSION), key.header(HEADER_KEY_VERSION))
+        && needed != have
+    {
+        return Err(format!("wrong key file: data needs Key-Version {needed}, key file has {have}"));
     }
+    let version = ring.for_armor(&data)?;
 
-    // (2) Load **ROTATED** bytes
-    let ascii = fs::read_to_string(wrapped_data_file)
+    let hkdf_key = open_bound_key::<N>(&key, &data, version.key())?;
+    decode_pipeline_v2_bytes::<N>(hkdf_key.expose_secret(), &data.data, salt, info)
+}
+
+/// [`decode_pipeline_v2_from_armor_files`] for signed files: the data and key
+/// blocks must both carry a valid signature by `signer` in `sig_file`
+/// (see `signing::sign_armor_files`). A swapped or altered file is reported
+/// as such, before the key is decrypted.
+#[cfg(feature = "std")]
+pub fn decode_pipeline_v2_from_signed_armor_files<const N: usize>(
+    wrapped_key_file: &str,
+    wrapped_data_file: &str,
+    sig_file: &str,
+    signer: &Ed25519PublicKey,
+    password: &SecretBytes,
+    salt: Option<&[u8]>,
+    info: &[u8],
+) -> Result<String, String> {
+    let sigs = read_armor_file_all(sig_file).map_err(|e| format!("read signatures: {e}"))?;
+    let data = read_armor_file(wrapped_data_file, LABEL_DATA)
         .map_err(|e| format!("read data: {e}"))?;
-    let rotated = decode_bytes_ascii_wrapped(&ascii, prefix, suffix)
-        .map_err(|e| format!("decode ascii: {e}"))?;
+    verify_armor(&data, &sigs, signer).map_err(|e| format!("data file: {e}"))?;
+    let key = read_armor_file(wrapped_key_file, LABEL_PROTECTED_KEY)
+        .map_err(|e| format!("read key: {e}"))?;
+    verify_armor(&key, &sigs, signer).map_err(|e| format!("key file: {e}"))?;
+
+    let hkdf_key = open_bound_key::<N>(&key, &data, password)?;
+    decode_pipeline_v2_bytes::<N>(hkdf_key.expose_secret(), &data.data, salt, info)
+}
+
+/// v2 decode where the rotated bytes arrive as symbol text
+/// (`PipelineV2Result::rotated_symbols`), e.g. pasted from a chat message.
+/// - wrapped_key_file: `BITMASHER PROTECTED KEY` block (contains HKDF key, len == N)
+/// - symbols: the symbol text; whitespace and emoji variation selectors are ignored
+#[cfg(feature = "std")]
+pub fn decode_pipeline_v2_from_symbols<const N: usize>(
+    wrapped_key_file: &str,
+    symbols: &str,
+    set: SymbolSet,
+    password: &SecretBytes,
+    salt: Option<&[u8]>,
+    info: &[u8],
+) -> Result<String, String> {
+    let rotated = decode_symbols(symbols, set).map_err(|e| format!("decode symbols: {e}"))?;
+
+    let key = read_armor_file(wrapped_key_file, LABEL_PROTECTED_KEY)
+        .map_err(|e| format!("key import: {e}"))?;
+    let hkdf_key = open_key::<N>(&key.data, password)?;
+    decode_pipeline_v2_bytes::<N>(hkdf_key.expose_secret(), &rotated, salt, info)
+}
+
+/// Decrypt the key block for `data`: a `Key-Id` mismatch between the two
+/// headers is reported before PBKDF2, and the decrypted key must have the
+/// `Key-Id` the data names.
+#[cfg(feature = "std")]
+fn open_bound_key<const N: usize>(key: &Armor, data: &Armor, password: &SecretBytes) -> Result<SecretBytes, String> {
+    open_key_for_id::<N>(&key.data, data.header(HEADER_KEY_ID), key.header(HEADER_KEY_ID), password)
+}
+
+/// [`open_key`] for data recorded with Key-Id `needed`: a key file recording
+/// another Key-Id (`have`) is rejected before decryption, and the decrypted
+/// key is checked too, for key files that record none.
+#[cfg(feature = "std")]
+fn open_key_for_id<const N: usize>(
+    blob: &[u8],
+    needed: Option<&str>,
+    have: Option<&str>,
+    password: &SecretBytes,
+) -> Result<SecretBytes, String> {
+    if let (Some(needed), Some(have)) = (needed, have)
+        && !needed.eq_ignore_ascii_case(have)
+    {
+        return Err(format!("wrong key file: data needs Key-Id {needed}, key file has Key-Id {have}"));
+    }
+    let hkdf_key = open_key::<N>(blob, password)?;
+    if let Some(needed) = needed {
+        let actual = Fingerprint::of_symmetric(hkdf_key.expose_secret()).key_id().to_string();
+        if !needed.eq_ignore_ascii_case(&actual) {
+            return Err(format!("wrong key file: data needs Key-Id {needed}, decrypted key has Key-Id {actual}"));
+        }
+    }
+    Ok(hkdf_key)
+}
+
+/// Decrypt a PPKE blob holding an `N`-byte key; the length is checked first.
+#[cfg(feature = "std")]
+fn open_key<const N: usize>(blob: &[u8], password: &SecretBytes) -> Result<SecretBytes, String> {
+    let len = protected_key_len(blob).map_err(|e| format!("key import: {e}"))?;
+    if len != N {
+        return Err(format!("wrong key file: key is {len} bytes, expected {N}"));
+    }
+    import_key_password_protected(blob, password).map_err(|e| format!("key import: {e}"))
+}
+
+/// In-memory v2 decode (`no_std` friendly), shared by the file decoders:
+/// undo rotations, verify HKDF, deinterleave.
+/// - hkdf_key: the decrypted HKDF key (len == N)
+/// - rotated: the **ROTATED** bytes (`PipelineV2Result::rotated_bytes`)
+pub fn decode_pipeline_v2_bytes<const N: usize>(
+    hkdf_key: &[u8],
+    rotated: &[u8],
+    salt: Option<&[u8]>,
+    info: &[u8],
+) -> Result<String, String> {
+    if hkdf_key.len() != N {
+        return Err(format!("hkdf_key length {} != N {}", hkdf_key.len(), N));
+    }
 
     // (3) Recreate BitArray from rotated bytes
     let mut storage = [0u8; N];
@@ -51,14 +246,14 @@ pub fn decode_pipeline_v2_from_files<const N: usize>(
     let mut bits = BitArray::<N>::new(storage, used * 8);
 
     // (4) Rebuild rotation schedule from hkdf_key bytes and inverse rotate (reverse order)
-    let ords = ordinals_from_key_bytes(&hkdf_key);
+    let ords = ordinals_from_key_bytes(hkdf_key);
     for &o in ords.iter().rev() {
         if o % 2 == 0 { bits.rotate_right(o); } else { bits.rotate_left(o); }
     }
 
     // (5) HKDF integrity check: re-derive HKDF on pre-rotation bytes and compare
     let rederived = hkdf_sha512_same_len(bits.as_bytes(), salt, info);
-    if rederived != hkdf_key {
+    if !ct_eq(rederived.expose_secret(), hkdf_key) {
         return Err("hkdf mismatch: decoded payload does not match the decrypted key".into());
     }
 
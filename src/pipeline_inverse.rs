@@ -6,6 +6,7 @@ use crate::BitArray;
 use crate::interleave::deinterleave_original_bytes;
 use crate::keygen::hkdf_sha512_same_len;
 use crate::pipeline::PipelineResult;
+use crate::secret::SecretBytes;
 
 /// Result of inverting the forward pipeline.
 pub struct InverseResult<const N: usize> {
@@ -16,7 +17,7 @@ pub struct InverseResult<const N: usize> {
     /// The reconstructed UTF-8 string (if decoding succeeded and not truncated mid-char).
     pub restored_original: Option<String>,
     /// HKDF re-derived over the restored pre-rotation bytes.
-    pub hkdf_key_rederived: Vec<u8>,
+    pub hkdf_key_rederived: SecretBytes,
     /// Whether the re-derived HKDF matches the forward pipeline’s HKDF.
     pub hkdf_matches: bool,
     /// Number of meaningful bytes (bit_len / 8).
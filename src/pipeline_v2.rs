@@ -1,10 +1,30 @@
 extern crate alloc;
 
-use alloc::{string::String, vec::Vec};
+use alloc::{string::{String, ToString}, vec::Vec};
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
+#[cfg(feature = "std")]
+use core::num::NonZeroU32;
 
+use rand::RngCore;
+
+#[cfg(feature = "std")]
+use crate::armor::{write_armor_file, Armor, HEADER_KEY_ID, HEADER_KEY_VERSION, HEADER_PIPELINE, LABEL_DATA};
 use crate::BitArray;
+#[cfg(feature = "std")]
+use crate::fingerprint::Fingerprint;
+#[cfg(feature = "std")]
+use crate::keyring::KeyRing;
+#[cfg(feature = "std")]
+use crate::ppke::{export_key_password_protected_armor_file, export_key_password_protected_ascii_file};
+#[cfg(feature = "std")]
+use crate::ascii_codec::encode_bytes_ascii_wrapped;
+#[cfg(feature = "std")]
 use crate::interleave::interleave_with_random_bytes;
+use crate::interleave::interleave_with_rng;
 use crate::keygen::hkdf_sha512_same_len;
+use crate::secret::SecretBytes;
+use crate::symbols::{encode_symbols, SymbolSet};
 
 /// Result object for v2 pipeline.
 #[derive(Clone)]
@@ -12,7 +32,7 @@ pub struct PipelineV2Result<const N: usize> {
     pub original: String,
     pub interleaved: Vec<u8>,    // raw interleaved bytes (before rotation)
     pub bitarray_final: BitArray<N>,
-    pub hkdf_key: Vec<u8>,       // HKDF-SHA512 over BitArray bytes (length = N)
+    pub hkdf_key: SecretBytes,   // HKDF-SHA512 over BitArray bytes (length = N)
     pub used_bytes: usize,       // interleaved bytes actually stored (<= N)
 }
 
@@ -28,13 +48,35 @@ fn ordinals_from_key_bytes(key: &[u8]) -> Vec<usize> {
 /// 3) HKDF-SHA512 over BitArray bytes -> hkdf_key (len = N)
 /// 4) ordinals := from hkdf_key bytes
 /// 5) Rotate BitArray:
-///      even o -> rotate_left(o), odd o -> rotate_right(o)
+///    even o -> rotate_left(o), odd o -> rotate_right(o)
+///
+/// Requires `std` (interleave filler seeded from system time); see
+/// [`process_str_pipeline_v2_with_rng`].
+#[cfg(feature = "std")]
 pub fn process_str_pipeline_v2<const N: usize>(
     input: &str,
     salt: Option<&[u8]>,
     info: &[u8],
 ) -> PipelineV2Result<N> {
-    let inter = interleave_with_random_bytes(input);
+    process_interleaved_v2::<N>(input, interleave_with_random_bytes(input), salt, info)
+}
+
+/// Same as [`process_str_pipeline_v2`], with interleave filler drawn from `rng`.
+pub fn process_str_pipeline_v2_with_rng<const N: usize>(
+    input: &str,
+    salt: Option<&[u8]>,
+    info: &[u8],
+    rng: &mut (impl RngCore + ?Sized),
+) -> PipelineV2Result<N> {
+    process_interleaved_v2::<N>(input, interleave_with_rng(input, rng), salt, info)
+}
+
+fn process_interleaved_v2<const N: usize>(
+    input: &str,
+    inter: Vec<u8>,
+    salt: Option<&[u8]>,
+    info: &[u8],
+) -> PipelineV2Result<N> {
 
     let mut storage = [0u8; N];
     let used = inter.len().min(N);
@@ -46,7 +88,7 @@ pub fn process_str_pipeline_v2<const N: usize>(
     let hkdf_key = hkdf_sha512_same_len(bits.as_bytes(), salt, info);
 
     // Rotation schedule derived from hkdf_key bytes
-    let ords = ordinals_from_key_bytes(&hkdf_key);
+    let ords = ordinals_from_key_bytes(hkdf_key.expose_secret());
     for &o in &ords {
         if o % 2 == 0 { bits.rotate_left(o); } else { bits.rotate_right(o); }
     }
@@ -59,3 +101,119 @@ pub fn process_str_pipeline_v2<const N: usize>(
         used_bytes: used,
     }
 }
+
+impl<const N: usize> PipelineV2Result<N> {
+    /// The **ROTATED** bytes that go into the data file (`used_bytes` of them).
+    pub fn rotated_bytes(&self) -> &[u8] {
+        &self.bitarray_final.as_bytes()[..self.used_bytes]
+    }
+
+    /// The rotated bytes as emoji/Unicode symbols (see `symbols`), for chat-safe
+    /// transport. Decode with `decode_pipeline_v2_from_symbols`.
+    pub fn rotated_symbols(&self, set: SymbolSet, per_line: usize) -> String {
+        encode_symbols(self.rotated_bytes(), set, per_line)
+    }
+}
+
+/// Export a v2 result as two ASCII-wrapped files, for
+/// `decode_pipeline_v2_from_files`:
+/// - key_path: PPKE over `hkdf_key`
+/// - data_path: rotated bytes
+///
+/// Each file starts with a `Key-Id: ...` line (the `Key-Id` of `hkdf_key`)
+/// right after `prefix`, so a mismatched pair is caught before any PBKDF2 work.
+#[cfg(feature = "std")]
+#[allow(clippy::too_many_arguments)]
+pub fn export_pipeline_v2_ascii_files<const N: usize>(
+    result: &PipelineV2Result<N>,
+    key_path: &str,
+    data_path: &str,
+    password: &SecretBytes,
+    per_line: usize,
+    prefix: &str,
+    suffix: &str,
+    iterations: Option<NonZeroU32>,
+    salt_len: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let key_id = Fingerprint::of_symmetric(result.hkdf_key.expose_secret()).key_id();
+    let prefix = alloc::format!("{prefix}{HEADER_KEY_ID}: {key_id}\n");
+    export_key_password_protected_ascii_file(
+        key_path,
+        result.hkdf_key.expose_secret(),
+        password,
+        per_line,
+        &prefix,
+        suffix,
+        iterations,
+        salt_len,
+    )?;
+    let ascii = encode_bytes_ascii_wrapped(result.rotated_bytes(), per_line, &prefix, suffix);
+    std::fs::write(data_path, ascii)?;
+    Ok(())
+}
+
+/// Export a v2 result as two armored files:
+/// - key_path: `BITMASHER PROTECTED KEY` (PPKE over `hkdf_key`)
+/// - data_path: `BITMASHER DATA` (rotated bytes)
+///
+/// Both carry a `Pipeline: v2` header and the `Key-Id` of `hkdf_key`, so a
+/// mismatched pair is caught before any PBKDF2 work.
+/// Decode with `decode_pipeline_v2_from_armor_files`.
+#[cfg(feature = "std")]
+pub fn export_pipeline_v2_armor_files<const N: usize>(
+    result: &PipelineV2Result<N>,
+    key_path: &str,
+    data_path: &str,
+    password: &SecretBytes,
+    iterations: Option<NonZeroU32>,
+    salt_len: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    export_armor_files(result, key_path, data_path, password, iterations, salt_len, &[])
+}
+
+/// [`export_pipeline_v2_armor_files`] with the keyring's active key as the
+/// PPKE secret instead of a password. Both blocks also carry its
+/// `Key-Version`, so the files keep opening after the ring is rotated.
+/// Decode with `decode_pipeline_v2_from_keyring_armor_files`.
+#[cfg(feature = "std")]
+pub fn export_pipeline_v2_armor_files_with_keyring<const N: usize>(
+    result: &PipelineV2Result<N>,
+    key_path: &str,
+    data_path: &str,
+    ring: &KeyRing,
+    iterations: Option<NonZeroU32>,
+    salt_len: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let active = ring.active()?;
+    let id = active.id.to_string();
+    let headers = [(HEADER_KEY_VERSION, id.as_str())];
+    export_armor_files(result, key_path, data_path, active.key(), iterations, salt_len, &headers)
+}
+
+#[cfg(feature = "std")]
+fn export_armor_files<const N: usize>(
+    result: &PipelineV2Result<N>,
+    key_path: &str,
+    data_path: &str,
+    password: &SecretBytes,
+    iterations: Option<NonZeroU32>,
+    salt_len: usize,
+    extra_headers: &[(&str, &str)],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let key_id = Fingerprint::of_symmetric(result.hkdf_key.expose_secret()).key_id().to_string();
+    let mut headers = alloc::vec![(HEADER_PIPELINE, "v2"), (HEADER_KEY_ID, key_id.as_str())];
+    headers.extend_from_slice(extra_headers);
+    export_key_password_protected_armor_file(
+        key_path,
+        result.hkdf_key.expose_secret(),
+        password,
+        &headers,
+        iterations,
+        salt_len,
+    )?;
+    let mut data = Armor::new(LABEL_DATA, result.rotated_bytes());
+    for (k, v) in &headers {
+        data.set_header(k, v);
+    }
+    write_armor_file(data_path, &data)
+}
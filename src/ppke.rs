@@ -17,16 +17,29 @@
 //!             ciphertext (pt_len + TAG(16) bytes)
 //!
 //! AAD during AEAD seal/open = the first 16 bytes (header) *only*.
+//!
+//! The PBKDF2-derived AEAD key and every plaintext buffer are wiped after
+//! use; decrypted keys are returned as [`SecretBytes`].
 
 extern crate alloc;
 
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "std")]
+use alloc::{boxed::Box, format, string::String};
 use core::num::NonZeroU32;
 
-use ring::{aead, pbkdf2, rand as ring_rand};
-use ring::rand::SecureRandom;
+use rand::{CryptoRng, RngCore};
+use ring::{aead, pbkdf2};
+use zeroize::Zeroizing;
+
+use crate::secret::SecretBytes;
 
+#[cfg(feature = "std")]
+use crate::armor::{read_armor_file, write_armor_file, Armor, LABEL_PROTECTED_KEY};
+#[cfg(feature = "std")]
 use crate::ascii_codec::{decode_bytes_ascii_wrapped, encode_bytes_ascii_wrapped};
+#[cfg(feature = "std")]
+use crate::paper::{decode_paper, encode_paper};
 
 /// Magic/version marker for PPKE v1
 const MAGIC: &[u8; 4] = b"BMK1";
@@ -59,9 +72,9 @@ fn read_u32_be(b: &[u8]) -> u32 {
 }
 
 /// Derive a 32-byte AEAD key using PBKDF2-HMAC-SHA256.
-fn derive_aead_key_pbkdf2(password: &[u8], salt: &[u8], iter: NonZeroU32) -> [u8; 32] {
-    let mut out = [0u8; 32];
-    pbkdf2::derive(pbkdf2::PBKDF2_HMAC_SHA256, iter, salt, password, &mut out);
+fn derive_aead_key_pbkdf2(password: &[u8], salt: &[u8], iter: NonZeroU32) -> Zeroizing<[u8; 32]> {
+    let mut out = Zeroizing::new([0u8; 32]);
+    pbkdf2::derive(pbkdf2::PBKDF2_HMAC_SHA256, iter, salt, password, out.as_mut());
     out
 }
 
@@ -73,11 +86,32 @@ fn derive_aead_key_pbkdf2(password: &[u8], salt: &[u8], iter: NonZeroU32) -> [u8
 /// - `salt_len`: recommended 16
 ///
 /// Returns the PPKE v1 binary payload. Suitable for ASCII armor via your ascii_codec.
+///
+/// Requires `std` (salt and nonce come from the OS CSPRNG); see
+/// [`export_key_password_protected_with_rng`] for `no_std`.
+#[cfg(feature = "std")]
 pub fn export_key_password_protected(
     plaintext_key: &[u8],
-    password: &[u8],
+    password: &SecretBytes,
     iterations: Option<NonZeroU32>,
     salt_len: usize,
+) -> Result<Vec<u8>, &'static str> {
+    export_key_password_protected_with_rng(
+        plaintext_key,
+        password,
+        iterations,
+        salt_len,
+        &mut rand::rngs::OsRng,
+    )
+}
+
+/// Like [`export_key_password_protected`], with salt and nonce drawn from `rng`.
+pub fn export_key_password_protected_with_rng<R: RngCore + CryptoRng + ?Sized>(
+    plaintext_key: &[u8],
+    password: &SecretBytes,
+    iterations: Option<NonZeroU32>,
+    salt_len: usize,
+    rng: &mut R,
 ) -> Result<Vec<u8>, &'static str> {
     if salt_len == 0 {
         return Err("salt_len must be > 0");
@@ -86,21 +120,20 @@ pub fn export_key_password_protected(
     let iter = iterations.unwrap_or_else(|| NonZeroU32::new(DEFAULT_PBKDF2_ITERATIONS).unwrap());
 
     // Random salt
-    let rng = ring_rand::SystemRandom::new();
     let mut salt = vec![0u8; salt_len];
-    rng.fill(&mut salt).map_err(|_| "rng salt")?;
+    rng.try_fill_bytes(&mut salt).map_err(|_| "rng salt")?;
 
     // Derive AEAD key
-    let aead_key_bytes = derive_aead_key_pbkdf2(password, &salt, iter);
+    let aead_key_bytes = derive_aead_key_pbkdf2(password.expose_secret(), &salt, iter);
 
     // AEAD setup
-    let unbound = aead::UnboundKey::new(&aead::CHACHA20_POLY1305, &aead_key_bytes)
+    let unbound = aead::UnboundKey::new(&aead::CHACHA20_POLY1305, aead_key_bytes.as_ref())
         .map_err(|_| "aead key init")?;
     let key = aead::LessSafeKey::new(unbound);
 
     // Nonce
     let mut nonce_bytes = [0u8; NONCE_LEN];
-    rng.fill(&mut nonce_bytes).map_err(|_| "rng nonce")?;
+    rng.try_fill_bytes(&mut nonce_bytes).map_err(|_| "rng nonce")?;
     let nonce = aead::Nonce::assume_unique_for_key(nonce_bytes);
 
     // Header (AAD)
@@ -114,12 +147,12 @@ pub fn export_key_password_protected(
     header.push(NONCE_LEN as u8);
     header.extend_from_slice(&u32_be(plaintext_key.len() as u32));
 
-    // Seal (in-place, append tag)
-    let mut buf = plaintext_key.to_vec();
+    // Seal (in-place, append tag); wiped if sealing fails midway
+    let mut buf = Zeroizing::new(plaintext_key.to_vec());
     key.seal_in_place_append_tag(
         nonce,
         aead::Aad::from(&header),
-        &mut buf,
+        &mut *buf,
     ).map_err(|_| "aead seal")?;
 
     // Assemble final payload
@@ -132,8 +165,20 @@ pub fn export_key_password_protected(
     Ok(out)
 }
 
+/// Length of the key inside a PPKE v1 `payload`, read from its header
+/// without the password (no PBKDF2 work).
+pub fn protected_key_len(payload: &[u8]) -> Result<usize, &'static str> {
+    if payload.len() < HEADER_LEN {
+        return Err("payload too short");
+    }
+    if &payload[0..4] != MAGIC {
+        return Err("bad magic");
+    }
+    Ok(read_u32_be(&payload[12..16]) as usize)
+}
+
 /// Decrypt a PPKE v1 binary `payload` using `password` and return the original key bytes.
-pub fn import_key_password_protected(payload: &[u8], password: &[u8]) -> Result<Vec<u8>, &'static str> {
+pub fn import_key_password_protected(payload: &[u8], password: &SecretBytes) -> Result<SecretBytes, &'static str> {
     if payload.len() < HEADER_LEN {
         return Err("payload too short");
     }
@@ -181,14 +226,14 @@ pub fn import_key_password_protected(payload: &[u8], password: &[u8]) -> Result<
 
     // Derive AEAD key
     let iter_nz = NonZeroU32::new(iter).ok_or("iterations == 0")?;
-    let aead_key_bytes = derive_aead_key_pbkdf2(password, salt, iter_nz);
+    let aead_key_bytes = derive_aead_key_pbkdf2(password.expose_secret(), salt, iter_nz);
 
-    let unbound = aead::UnboundKey::new(&aead::CHACHA20_POLY1305, &aead_key_bytes)
+    let unbound = aead::UnboundKey::new(&aead::CHACHA20_POLY1305, aead_key_bytes.as_ref())
         .map_err(|_| "aead key init")?;
     let key = aead::LessSafeKey::new(unbound);
 
-    // Mutable ciphertext buffer for in-place open
-    let mut ct = ciphertext.to_vec();
+    // Mutable ciphertext buffer for in-place open (holds the plaintext afterwards)
+    let mut ct = SecretBytes::from(ciphertext);
 
     // Nonce + AAD must match exactly how we sealed
     let nonce = aead::Nonce::try_assume_unique_for_key(nonce_bytes)
@@ -199,19 +244,21 @@ pub fn import_key_password_protected(payload: &[u8], password: &[u8]) -> Result<
     let pt = key.open_in_place(
         nonce,
         aad,
-        &mut ct,
+        ct.expose_secret_mut(),
     ).map_err(|_| "aead open")?;
 
-    Ok(pt.to_vec())
+    Ok(SecretBytes::from(&*pt))
 }
 
 // --------------------- Public API: ASCII-armor file helpers (std only) ---------------------
 
 /// Export a password-protected key to an ASCII-wrapped file.
+#[cfg(feature = "std")]
+#[allow(clippy::too_many_arguments)]
 pub fn export_key_password_protected_ascii_file(
     path: &str,
     plaintext_key: &[u8],
-    password: &[u8],
+    password: &SecretBytes,
     per_line: usize,
     prefix: &str,
     suffix: &str,
@@ -227,12 +274,13 @@ pub fn export_key_password_protected_ascii_file(
 }
 
 /// Import a password-protected key from an ASCII-wrapped file.
+#[cfg(feature = "std")]
 pub fn import_key_password_protected_ascii_file(
     path: &str,
-    password: &[u8],
+    password: &SecretBytes,
     prefix: &str,
     suffix: &str,
-) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+) -> Result<SecretBytes, Box<dyn std::error::Error>> {
     use std::fs;
 
     let armored = fs::read_to_string(path)?;
@@ -242,3 +290,65 @@ pub fn import_key_password_protected_ascii_file(
         .map_err(|e| format!("decrypt: {e}"))?;
     Ok(key)
 }
+
+/// Export a password-protected key to an armored file
+/// (`-----BEGIN BITMASHER PROTECTED KEY-----`, headers, CRC-24).
+/// `headers` are added after the default `Version` header.
+#[cfg(feature = "std")]
+pub fn export_key_password_protected_armor_file(
+    path: &str,
+    plaintext_key: &[u8],
+    password: &SecretBytes,
+    headers: &[(&str, &str)],
+    iterations: Option<NonZeroU32>,
+    salt_len: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let blob = export_key_password_protected(plaintext_key, password, iterations, salt_len)?;
+    let mut armor = Armor::new(LABEL_PROTECTED_KEY, &blob);
+    for (k, v) in headers {
+        armor.set_header(k, v);
+    }
+    write_armor_file(path, &armor)
+}
+
+/// Import a password-protected key from an armored file.
+/// The label and CRC-24 are checked before any key derivation.
+#[cfg(feature = "std")]
+pub fn import_key_password_protected_armor_file(
+    path: &str,
+    password: &SecretBytes,
+) -> Result<SecretBytes, Box<dyn std::error::Error>> {
+    let armor = read_armor_file(path, LABEL_PROTECTED_KEY)?;
+    let key = import_key_password_protected(&armor.data, password)
+        .map_err(|e| format!("decrypt: {e}"))?;
+    Ok(key)
+}
+
+// --------------------- Public API: paper backup ---------------------
+
+/// Export a password-protected key as a printable paper backup
+/// (see `paper`): numbered hex lines, each with its own CRC-24.
+#[cfg(feature = "std")]
+pub fn export_key_password_protected_paper(
+    plaintext_key: &[u8],
+    password: &SecretBytes,
+    per_line: usize,
+    iterations: Option<NonZeroU32>,
+    salt_len: usize,
+) -> Result<String, &'static str> {
+    let blob = export_key_password_protected(plaintext_key, password, iterations, salt_len)?;
+    Ok(encode_paper(&blob, per_line))
+}
+
+/// Import a key typed back from a paper backup.
+/// Typos are reported by line before any key derivation is attempted.
+#[cfg(feature = "std")]
+pub fn import_key_password_protected_paper(
+    text: &str,
+    password: &SecretBytes,
+) -> Result<SecretBytes, Box<dyn std::error::Error>> {
+    let blob = decode_paper(text)?;
+    let key = import_key_password_protected(&blob, password)
+        .map_err(|e| format!("decrypt: {e}"))?;
+    Ok(key)
+}
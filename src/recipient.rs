@@ -0,0 +1,272 @@
+//! recipient.rs — wrap keys to X25519 public keys
+//!
+//! Password-free counterpart of `ppke`: a key is sealed to one or more
+//! recipients' X25519 public keys and opened with the matching private key.
+//!
+//! - A random 32-byte content key (CEK) encrypts the payload with
+//!   ChaCha20-Poly1305.
+//! - For each recipient, a fresh ephemeral X25519 key agrees a shared secret
+//!   with the recipient's static key; HKDF-SHA256 turns it into a wrap key
+//!   (salt = ephemeral public ‖ recipient public, info = `WRAP_INFO`) that
+//!   seals the CEK with ChaCha20-Poly1305 and an all-zero nonce (each wrap
+//!   key is used once).
+//!
+//! Static private keys need `x25519-dalek`: ring's X25519 keys are
+//! ephemeral only. HKDF and the AEAD are ring's.
+//!
+//! Binary layout:
+//!
+//! ```text
+//! MAGIC "BMX1" | count u8
+//! count × ( ephemeral_public 32 | wrapped_cek 32 + TAG 16 )
+//! nonce 12 | ciphertext (key_len + TAG 16)
+//! ```
+//!
+//! Everything before the nonce is the AAD of the payload, so stanzas can
+//! be neither dropped nor swapped. Opening tries each stanza in turn.
+
+extern crate alloc;
+
+use alloc::{format, string::{String, ToString}, vec::Vec};
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
+use core::fmt;
+
+use rand::{CryptoRng, RngCore};
+use ring::{aead, hkdf};
+use x25519_dalek::{PublicKey, StaticSecret};
+use zeroize::Zeroizing;
+
+use crate::armor::{Armor, HEADER_ALGORITHM, HEADER_KEY_ID, LABEL_PUBLIC_KEY};
+#[cfg(feature = "std")]
+use crate::armor::{read_armor_file, write_armor_file, LABEL_SEALED_KEY};
+use crate::fingerprint::Fingerprint;
+use crate::secret::SecretBytes;
+
+const MAGIC: &[u8; 4] = b"BMX1";
+const WRAP_INFO: &[u8] = b"bitmasher:x25519-wrap:v1";
+/// `Algorithm` header value of X25519 public keys.
+pub const ALGORITHM_X25519: &str = "X25519";
+
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+const STANZA_LEN: usize = KEY_LEN + KEY_LEN + TAG_LEN;
+
+/// A recipient's public key.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct X25519PublicKey([u8; KEY_LEN]);
+
+/// A recipient's private key. Wiped on drop; `Debug` shows only the public key.
+#[derive(Clone)]
+pub struct X25519SecretKey(StaticSecret);
+
+impl X25519PublicKey {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        Ok(X25519PublicKey(bytes.try_into().map_err(|_| "X25519 public key must be 32 bytes")?))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; KEY_LEN] {
+        &self.0
+    }
+
+    pub fn fingerprint(&self) -> Fingerprint {
+        Fingerprint::new(ALGORITHM_X25519, &self.0)
+    }
+
+    /// As a `BITMASHER PUBLIC KEY` block with `Algorithm: X25519` and `Key-Id`.
+    pub fn to_armor(&self) -> Armor {
+        Armor::new(LABEL_PUBLIC_KEY, &self.0)
+            .with_header(HEADER_ALGORITHM, ALGORITHM_X25519)
+            .with_header(HEADER_KEY_ID, &self.fingerprint().key_id().to_string())
+    }
+
+    /// Inverse of [`X25519PublicKey::to_armor`].
+    pub fn from_armor(armor: &Armor) -> Result<Self, String> {
+        armor.expect_label(LABEL_PUBLIC_KEY)?;
+        match armor.header(HEADER_ALGORITHM) {
+            Some(ALGORITHM_X25519) => {}
+            other => return Err(format!("expected an {ALGORITHM_X25519} public key, found {other:?}")),
+        }
+        Ok(Self::from_bytes(&armor.data)?)
+    }
+}
+
+impl fmt::Debug for X25519PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "X25519PublicKey(")?;
+        for b in &self.0 {
+            write!(f, "{b:02x}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl X25519SecretKey {
+    /// New random private key from the OS CSPRNG.
+    #[cfg(feature = "std")]
+    pub fn generate() -> Self {
+        Self::generate_with_rng(&mut rand::rngs::OsRng)
+    }
+
+    /// New random private key from `rng`.
+    pub fn generate_with_rng<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> Self {
+        let mut bytes = Zeroizing::new([0u8; KEY_LEN]);
+        rng.fill_bytes(bytes.as_mut());
+        X25519SecretKey(StaticSecret::from(*bytes))
+    }
+
+    /// From the 32 bytes returned by [`X25519SecretKey::to_secret_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        let bytes: Zeroizing<[u8; KEY_LEN]> =
+            Zeroizing::new(bytes.try_into().map_err(|_| "X25519 private key must be 32 bytes")?);
+        Ok(X25519SecretKey(StaticSecret::from(*bytes)))
+    }
+
+    /// The private key bytes, e.g. to protect with `ppke`.
+    pub fn to_secret_bytes(&self) -> SecretBytes {
+        SecretBytes::from(self.0.as_bytes().as_slice())
+    }
+
+    pub fn public_key(&self) -> X25519PublicKey {
+        X25519PublicKey(PublicKey::from(&self.0).to_bytes())
+    }
+}
+
+impl fmt::Debug for X25519SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("X25519SecretKey")
+            .field("public", &self.public_key())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Seal `key` to every key in `recipients` (1..=255), with ephemeral keys,
+/// CEK and nonce from the OS CSPRNG.
+#[cfg(feature = "std")]
+pub fn seal_to_recipients(key: &[u8], recipients: &[X25519PublicKey]) -> Result<Vec<u8>, &'static str> {
+    seal_to_recipients_with_rng(key, recipients, &mut rand::rngs::OsRng)
+}
+
+/// Like [`seal_to_recipients`], with all randomness drawn from `rng`.
+pub fn seal_to_recipients_with_rng<R: RngCore + CryptoRng + ?Sized>(
+    key: &[u8],
+    recipients: &[X25519PublicKey],
+    rng: &mut R,
+) -> Result<Vec<u8>, &'static str> {
+    if recipients.is_empty() {
+        return Err("no recipients");
+    }
+    let count = u8::try_from(recipients.len()).map_err(|_| "too many recipients (max 255)")?;
+
+    let mut cek = Zeroizing::new([0u8; KEY_LEN]);
+    rng.try_fill_bytes(cek.as_mut()).map_err(|_| "rng cek")?;
+
+    let mut out = Vec::with_capacity(5 + recipients.len() * STANZA_LEN + NONCE_LEN + key.len() + TAG_LEN);
+    out.extend_from_slice(MAGIC);
+    out.push(count);
+    for recipient in recipients {
+        let ephemeral = X25519SecretKey::generate_with_rng(rng);
+        let epk = ephemeral.public_key();
+        let shared = ephemeral.0.diffie_hellman(&PublicKey::from(recipient.0));
+        if !shared.was_contributory() {
+            return Err("invalid recipient public key");
+        }
+        let wrap = wrap_key(shared.as_bytes(), &epk, recipient)?;
+        let mut wrapped = cek.to_vec();
+        wrap.seal_in_place_append_tag(aead::Nonce::assume_unique_for_key([0u8; NONCE_LEN]), aead::Aad::empty(), &mut wrapped)
+            .map_err(|_| "aead seal")?;
+        out.extend_from_slice(&epk.0);
+        out.extend_from_slice(&wrapped);
+    }
+
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.try_fill_bytes(&mut nonce).map_err(|_| "rng nonce")?;
+    let aad_len = out.len();
+    let mut buf = Zeroizing::new(key.to_vec());
+    aead_key(cek.as_ref())?
+        .seal_in_place_append_tag(aead::Nonce::assume_unique_for_key(nonce), aead::Aad::from(&out[..aad_len]), &mut *buf)
+        .map_err(|_| "aead seal")?;
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&buf);
+    Ok(out)
+}
+
+/// Open a blob from [`seal_to_recipients`] with one recipient's private key.
+pub fn open_with_secret_key(sealed: &[u8], secret: &X25519SecretKey) -> Result<SecretBytes, &'static str> {
+    if sealed.len() < 5 || &sealed[..4] != MAGIC {
+        return Err("bad magic");
+    }
+    let count = sealed[4] as usize;
+    let payload_at = 5 + count * STANZA_LEN;
+    if count == 0 || sealed.len() < payload_at + NONCE_LEN + TAG_LEN {
+        return Err("sealed key truncated");
+    }
+
+    let public = secret.public_key();
+    let mut cek = None;
+    for stanza in sealed[5..payload_at].chunks(STANZA_LEN) {
+        let epk = X25519PublicKey::from_bytes(&stanza[..KEY_LEN])?;
+        let shared = secret.0.diffie_hellman(&PublicKey::from(epk.0));
+        if !shared.was_contributory() {
+            continue;
+        }
+        let mut wrapped = Zeroizing::new(stanza[KEY_LEN..].to_vec());
+        let opened = wrap_key(shared.as_bytes(), &epk, &public)?.open_in_place(
+            aead::Nonce::assume_unique_for_key([0u8; NONCE_LEN]),
+            aead::Aad::empty(),
+            &mut wrapped,
+        );
+        if let Ok(k) = opened {
+            cek = Some(Zeroizing::new(k.to_vec()));
+            break;
+        }
+    }
+    let cek = cek.ok_or("not sealed to this key")?;
+
+    let nonce = aead::Nonce::try_assume_unique_for_key(&sealed[payload_at..payload_at + NONCE_LEN])
+        .map_err(|_| "nonce size")?;
+    let mut ct = SecretBytes::from(&sealed[payload_at + NONCE_LEN..]);
+    let pt = aead_key(&cek)?
+        .open_in_place(nonce, aead::Aad::from(&sealed[..payload_at]), ct.expose_secret_mut())
+        .map_err(|_| "aead open")?;
+    Ok(SecretBytes::from(&*pt))
+}
+
+/// Seal `key` to `recipients` and write a `BITMASHER SEALED KEY` armored file.
+#[cfg(feature = "std")]
+pub fn seal_key_armor_file(
+    path: &str,
+    key: &[u8],
+    recipients: &[X25519PublicKey],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let blob = seal_to_recipients(key, recipients)?;
+    write_armor_file(path, &Armor::new(LABEL_SEALED_KEY, &blob))
+}
+
+/// Open a file written by [`seal_key_armor_file`].
+#[cfg(feature = "std")]
+pub fn open_key_armor_file(path: &str, secret: &X25519SecretKey) -> Result<SecretBytes, Box<dyn std::error::Error>> {
+    let armor = read_armor_file(path, LABEL_SEALED_KEY)?;
+    Ok(open_with_secret_key(&armor.data, secret)?)
+}
+
+fn wrap_key(
+    shared: &[u8; KEY_LEN],
+    epk: &X25519PublicKey,
+    recipient: &X25519PublicKey,
+) -> Result<aead::LessSafeKey, &'static str> {
+    let salt = [epk.0, recipient.0].concat();
+    let mut okm = Zeroizing::new([0u8; KEY_LEN]);
+    hkdf::Salt::new(hkdf::HKDF_SHA256, &salt)
+        .extract(shared)
+        .expand(&[WRAP_INFO], &aead::CHACHA20_POLY1305)
+        .and_then(|k| k.fill(okm.as_mut()))
+        .map_err(|_| "hkdf")?;
+    aead_key(okm.as_ref())
+}
+
+fn aead_key(key: &[u8]) -> Result<aead::LessSafeKey, &'static str> {
+    let unbound = aead::UnboundKey::new(&aead::CHACHA20_POLY1305, key).map_err(|_| "aead key init")?;
+    Ok(aead::LessSafeKey::new(unbound))
+}
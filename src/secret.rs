@@ -0,0 +1,110 @@
+//! secret.rs — key material that is wiped when dropped
+//!
+//! `SecretBytes` owns secret bytes (derived keys, decrypted PPKE keys,
+//! passwords). It:
+//!
+//! - overwrites its buffer with zeros on drop (via `zeroize`, which the
+//!   optimizer may not elide),
+//! - prints only its length in `Debug`,
+//! - hands out the bytes only through the explicit [`SecretBytes::expose_secret`],
+//! - compares in constant time.
+//!
+//! Password parameters throughout the crate are `&SecretBytes` too, so the
+//! caller's copy is wiped as well (`SecretBytes::from(String)` takes over a
+//! password read as text without copying it). The crate never copies a
+//! password, and wipes everything derived from it.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use zeroize::Zeroize;
+
+/// Owned secret bytes; see the module docs.
+#[derive(Clone, Default)]
+pub struct SecretBytes(Vec<u8>);
+
+impl SecretBytes {
+    /// Take ownership of `bytes` (no copy is left behind).
+    pub fn new(bytes: Vec<u8>) -> Self {
+        SecretBytes(bytes)
+    }
+
+    /// `len` zero bytes, to be filled through [`SecretBytes::expose_secret_mut`].
+    pub fn zeroed(len: usize) -> Self {
+        SecretBytes(alloc::vec![0u8; len])
+    }
+
+    /// Borrow the secret. The name is meant to stand out in review.
+    pub fn expose_secret(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Mutably borrow the secret (e.g. to fill it from an RNG or KDF).
+    pub fn expose_secret_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<u8>> for SecretBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        SecretBytes(bytes)
+    }
+}
+
+impl From<&[u8]> for SecretBytes {
+    fn from(bytes: &[u8]) -> Self {
+        SecretBytes(bytes.to_vec())
+    }
+}
+
+impl<const N: usize> From<&[u8; N]> for SecretBytes {
+    fn from(bytes: &[u8; N]) -> Self {
+        SecretBytes(bytes.to_vec())
+    }
+}
+
+impl From<alloc::string::String> for SecretBytes {
+    fn from(s: alloc::string::String) -> Self {
+        SecretBytes(s.into_bytes())
+    }
+}
+
+impl Drop for SecretBytes {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretBytes([REDACTED; {}])", self.0.len())
+    }
+}
+
+/// Constant time for equal lengths (the length itself is not secret).
+impl PartialEq for SecretBytes {
+    fn eq(&self, other: &Self) -> bool {
+        ct_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SecretBytes {}
+
+/// Compare without an early exit on the first differing byte.
+pub(crate) fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    core::hint::black_box(diff) == 0
+}
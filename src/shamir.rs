@@ -0,0 +1,215 @@
+//! shamir.rs — k-of-n Shamir secret sharing over GF(256)
+//!
+//! Splits a key (e.g. a v2 `hkdf_key`) into `n` shares so that any
+//! `threshold` of them recombine it and fewer reveal nothing about it.
+//! Each byte of the secret is the constant term of its own random
+//! polynomial of degree `threshold - 1`; share `x` holds the polynomial
+//! values at `x` (1..=255). Field: GF(2^8) with the AES polynomial 0x11B,
+//! computed without lookup tables.
+//!
+//! Every share of one split carries the same random 64-bit *set id*, so
+//! shares of different splits are rejected instead of combining into garbage.
+//! Shares export as `BITMASHER KEY SHARE` armored blocks with `Share-Index`,
+//! `Share-Threshold`, `Share-Count` and `Share-Set` headers.
+
+extern crate alloc;
+
+use alloc::{format, string::{String, ToString}, vec::Vec};
+
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroizing;
+
+use crate::armor::{
+    Armor, HEADER_SHARE_COUNT, HEADER_SHARE_INDEX, HEADER_SHARE_SET, HEADER_SHARE_THRESHOLD,
+    LABEL_KEY_SHARE,
+};
+use crate::secret::SecretBytes;
+
+/// One share of a split secret.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Share {
+    index: u8,
+    threshold: u8,
+    count: u8,
+    set_id: u64,
+    data: SecretBytes,
+}
+
+impl Share {
+    /// x coordinate of this share (1..=255).
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Shares needed to recombine.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Shares made by the split.
+    pub fn count(&self) -> u8 {
+        self.count
+    }
+
+    /// Random id common to all shares of one split.
+    pub fn set_id(&self) -> u64 {
+        self.set_id
+    }
+
+    pub fn expose_secret(&self) -> &[u8] {
+        self.data.expose_secret()
+    }
+
+    /// As a `BITMASHER KEY SHARE` armored block.
+    pub fn to_armor(&self) -> Armor {
+        Armor::new(LABEL_KEY_SHARE, self.data.expose_secret())
+            .with_header(HEADER_SHARE_INDEX, &self.index.to_string())
+            .with_header(HEADER_SHARE_THRESHOLD, &self.threshold.to_string())
+            .with_header(HEADER_SHARE_COUNT, &self.count.to_string())
+            .with_header(HEADER_SHARE_SET, &format!("{:016X}", self.set_id))
+    }
+
+    /// Inverse of [`Share::to_armor`]; label and metadata are checked.
+    pub fn from_armor(armor: &Armor) -> Result<Share, String> {
+        armor.expect_label(LABEL_KEY_SHARE)?;
+        let header = |key: &str| armor.header(key).ok_or_else(|| format!("share armor has no {key} header"));
+        let number = |key: &str| {
+            let v = header(key)?;
+            v.parse::<u8>().map_err(|_| format!("invalid {key} header '{v}'"))
+        };
+        let set = header(HEADER_SHARE_SET)?;
+        let share = Share {
+            index: number(HEADER_SHARE_INDEX)?,
+            threshold: number(HEADER_SHARE_THRESHOLD)?,
+            count: number(HEADER_SHARE_COUNT)?,
+            set_id: u64::from_str_radix(set, 16).map_err(|_| format!("invalid {HEADER_SHARE_SET} header '{set}'"))?,
+            data: SecretBytes::from(armor.data.as_slice()),
+        };
+        check_params(share.threshold, share.count)?;
+        if share.index == 0 || share.index > share.count {
+            return Err(format!("share index {} out of range 1..={}", share.index, share.count));
+        }
+        Ok(share)
+    }
+}
+
+/// Split `secret` into `count` shares, any `threshold` of which recombine it
+/// (2 <= threshold <= count <= 255). Randomness comes from the OS CSPRNG.
+#[cfg(feature = "std")]
+pub fn split_secret(secret: &[u8], threshold: u8, count: u8) -> Result<Vec<Share>, String> {
+    split_secret_with_rng(secret, threshold, count, &mut rand::rngs::OsRng)
+}
+
+/// Like [`split_secret`], with polynomial coefficients and set id drawn from `rng`.
+pub fn split_secret_with_rng<R: RngCore + CryptoRng + ?Sized>(
+    secret: &[u8],
+    threshold: u8,
+    count: u8,
+    rng: &mut R,
+) -> Result<Vec<Share>, String> {
+    check_params(threshold, count)?;
+    if secret.is_empty() {
+        return Err("secret must not be empty".into());
+    }
+
+    let set_id = rng.next_u64();
+    let mut shares: Vec<Share> = (1..=count)
+        .map(|index| Share { index, threshold, count, set_id, data: SecretBytes::zeroed(secret.len()) })
+        .collect();
+
+    // coeffs[0] = secret byte, coeffs[1..] random
+    let mut coeffs = Zeroizing::new(alloc::vec![0u8; threshold as usize]);
+    for (i, &s) in secret.iter().enumerate() {
+        coeffs[0] = s;
+        rng.try_fill_bytes(&mut coeffs[1..]).map_err(|_| "rng coefficients".to_string())?;
+        for share in &mut shares {
+            // Horner's rule
+            let y = coeffs.iter().rev().fold(0u8, |acc, &c| gf_mul(acc, share.index) ^ c);
+            share.data.expose_secret_mut()[i] = y;
+        }
+    }
+    Ok(shares)
+}
+
+/// Recombine a secret from at least `threshold` shares of one split.
+/// Fails on missing, duplicate or mismatched shares.
+pub fn combine_shares(shares: &[Share]) -> Result<SecretBytes, String> {
+    let first = shares.first().ok_or("no shares given")?;
+    for s in shares {
+        if s.set_id != first.set_id {
+            return Err(format!("share {} belongs to a different split", s.index));
+        }
+        if (s.threshold, s.count, s.data.len()) != (first.threshold, first.count, first.data.len()) {
+            return Err(format!("share {} metadata does not match share {}", s.index, first.index));
+        }
+        if s.index == 0 {
+            return Err("share index 0 is invalid".into());
+        }
+    }
+    for (i, s) in shares.iter().enumerate() {
+        if shares[..i].iter().any(|t| t.index == s.index) {
+            return Err(format!("duplicate share {}", s.index));
+        }
+    }
+    let k = first.threshold as usize;
+    if shares.len() < k {
+        return Err(format!("need {k} shares, got {}", shares.len()));
+    }
+
+    // Lagrange interpolation at x = 0 over the first k shares
+    let used = &shares[..k];
+    let weights: Vec<u8> = used
+        .iter()
+        .map(|s| {
+            let (num, den) = used.iter().filter(|t| t.index != s.index).fold((1u8, 1u8), |(num, den), t| {
+                (gf_mul(num, t.index), gf_mul(den, t.index ^ s.index))
+            });
+            gf_mul(num, gf_inv(den))
+        })
+        .collect();
+    let mut secret = SecretBytes::zeroed(first.data.len());
+    for (i, out) in secret.expose_secret_mut().iter_mut().enumerate() {
+        *out = used
+            .iter()
+            .zip(&weights)
+            .fold(0u8, |acc, (s, &w)| acc ^ gf_mul(s.data.expose_secret()[i], w));
+    }
+    Ok(secret)
+}
+
+fn check_params(threshold: u8, count: u8) -> Result<(), String> {
+    if threshold < 2 {
+        return Err("threshold must be at least 2".into());
+    }
+    if threshold > count {
+        return Err(format!("threshold {threshold} exceeds share count {count}"));
+    }
+    Ok(())
+}
+
+/// Multiplication in GF(2^8) mod x^8 + x^4 + x^3 + x + 1, branch-free.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut p = 0u8;
+    for _ in 0..8 {
+        p ^= a & 0u8.wrapping_sub(b & 1);
+        let carry = 0u8.wrapping_sub(a >> 7);
+        a = (a << 1) ^ (0x1B & carry);
+        b >>= 1;
+    }
+    p
+}
+
+/// Inverse as a^254 (a != 0).
+fn gf_inv(a: u8) -> u8 {
+    let mut result = 1u8;
+    let mut base = a;
+    let mut e = 254u8;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = gf_mul(result, base);
+        }
+        base = gf_mul(base, base);
+        e >>= 1;
+    }
+    result
+}
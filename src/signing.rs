@@ -0,0 +1,249 @@
+//! signing.rs — Ed25519 detached signatures over armored artifacts
+//!
+//! PPKE gives secrecy, not authenticity: anyone can swap the rotated data
+//! file, and the receiver only sees an HKDF mismatch. Signing each armored
+//! block (key and data) lets the receiver reject a forged or swapped file
+//! before any PBKDF2 work.
+//!
+//! A signature covers the block's *content*, not its text: label, headers
+//! and data, in this canonical encoding (`lp(x)` = u32be(len) ‖ x):
+//!
+//! ```text
+//! "bitmasher:armor-signature:v1\0" ‖ lp(label) ‖ u32be(header count)
+//!   ‖ lp(name) ‖ lp(value) for each header ‖ lp(data)
+//! ```
+//!
+//! `Version` and the `Fec-*` transport headers are left out, so re-wrapping
+//! lines, re-encoding with another crate version or adding FEC keep the
+//! signature valid. Signatures are `BITMASHER SIGNATURE` blocks with
+//! `Algorithm: Ed25519`, `Signed-Label` (the label of the signed block) and
+//! `Key-Id` (the signer's); one signature file may hold several of them.
+//!
+//! All Ed25519 operations are ring's.
+
+extern crate alloc;
+
+use alloc::{format, string::{String, ToString}, vec::Vec};
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
+use core::fmt;
+
+use rand::{CryptoRng, RngCore};
+use ring::signature::{self, Ed25519KeyPair, KeyPair};
+use zeroize::Zeroizing;
+
+use crate::armor::{
+    Armor, HEADER_ALGORITHM, HEADER_FEC_LENGTH, HEADER_FEC_PARITY, HEADER_KEY_ID, HEADER_SIGNED_LABEL,
+    HEADER_VERSION, LABEL_PUBLIC_KEY, LABEL_SIGNATURE,
+};
+#[cfg(feature = "std")]
+use crate::armor::{read_armor_file, read_armor_file_all, write_armor_file_all};
+use crate::fingerprint::Fingerprint;
+use crate::secret::SecretBytes;
+
+/// `Algorithm` header value of Ed25519 public keys and signatures.
+pub const ALGORITHM_ED25519: &str = "Ed25519";
+
+const SIGNATURE_CONTEXT: &[u8] = b"bitmasher:armor-signature:v1\0";
+const SEED_LEN: usize = 32;
+const PUBLIC_KEY_LEN: usize = 32;
+pub const SIGNATURE_LEN: usize = 64;
+
+/// An Ed25519 verification key.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Ed25519PublicKey([u8; PUBLIC_KEY_LEN]);
+
+/// An Ed25519 signing key, kept as its 32-byte seed (RFC 8032 private key).
+pub struct Ed25519SecretKey {
+    seed: SecretBytes,
+    pair: Ed25519KeyPair,
+}
+
+impl Ed25519PublicKey {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        Ok(Ed25519PublicKey(bytes.try_into().map_err(|_| "Ed25519 public key must be 32 bytes")?))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; PUBLIC_KEY_LEN] {
+        &self.0
+    }
+
+    /// Check `signature` over `message`.
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), &'static str> {
+        signature::UnparsedPublicKey::new(&signature::ED25519, &self.0)
+            .verify(message, signature)
+            .map_err(|_| "bad signature")
+    }
+
+    pub fn fingerprint(&self) -> Fingerprint {
+        Fingerprint::new(ALGORITHM_ED25519, &self.0)
+    }
+
+    /// As a `BITMASHER PUBLIC KEY` block with `Algorithm: Ed25519` and `Key-Id`.
+    pub fn to_armor(&self) -> Armor {
+        Armor::new(LABEL_PUBLIC_KEY, &self.0)
+            .with_header(HEADER_ALGORITHM, ALGORITHM_ED25519)
+            .with_header(HEADER_KEY_ID, &self.fingerprint().key_id().to_string())
+    }
+
+    /// Inverse of [`Ed25519PublicKey::to_armor`].
+    pub fn from_armor(armor: &Armor) -> Result<Self, String> {
+        armor.expect_label(LABEL_PUBLIC_KEY)?;
+        match armor.header(HEADER_ALGORITHM) {
+            Some(ALGORITHM_ED25519) => {}
+            other => return Err(format!("expected an {ALGORITHM_ED25519} public key, found {other:?}")),
+        }
+        Ok(Self::from_bytes(&armor.data)?)
+    }
+}
+
+impl fmt::Debug for Ed25519PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ed25519PublicKey(")?;
+        for b in &self.0 {
+            write!(f, "{b:02x}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl Ed25519SecretKey {
+    /// New random signing key from the OS CSPRNG.
+    #[cfg(feature = "std")]
+    pub fn generate() -> Self {
+        Self::generate_with_rng(&mut rand::rngs::OsRng)
+    }
+
+    /// New random signing key from `rng`.
+    pub fn generate_with_rng<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> Self {
+        let mut seed = Zeroizing::new([0u8; SEED_LEN]);
+        rng.fill_bytes(seed.as_mut());
+        Self::from_seed(seed.as_ref()).expect("seed has the right length")
+    }
+
+    /// From the 32-byte seed returned by [`Ed25519SecretKey::to_secret_bytes`].
+    pub fn from_seed(seed: &[u8]) -> Result<Self, &'static str> {
+        if seed.len() != SEED_LEN {
+            return Err("Ed25519 seed must be 32 bytes");
+        }
+        let pair = Ed25519KeyPair::from_seed_unchecked(seed).map_err(|_| "invalid Ed25519 seed")?;
+        Ok(Self { seed: SecretBytes::from(seed), pair })
+    }
+
+    /// The seed, e.g. to protect with `ppke`.
+    pub fn to_secret_bytes(&self) -> SecretBytes {
+        self.seed.clone()
+    }
+
+    pub fn public_key(&self) -> Ed25519PublicKey {
+        Ed25519PublicKey::from_bytes(self.pair.public_key().as_ref()).expect("Ed25519 public keys are 32 bytes")
+    }
+
+    /// Sign `message` (deterministic, RFC 8032).
+    pub fn sign(&self, message: &[u8]) -> [u8; SIGNATURE_LEN] {
+        let mut out = [0u8; SIGNATURE_LEN];
+        out.copy_from_slice(self.pair.sign(message).as_ref());
+        out
+    }
+}
+
+impl Clone for Ed25519SecretKey {
+    fn clone(&self) -> Self {
+        Self::from_seed(self.seed.expose_secret()).expect("seed was valid")
+    }
+}
+
+impl fmt::Debug for Ed25519SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ed25519SecretKey")
+            .field("public", &self.public_key())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Detached signature block over `armor`.
+pub fn sign_armor(armor: &Armor, key: &Ed25519SecretKey) -> Armor {
+    Armor::new(LABEL_SIGNATURE, &key.sign(&signed_message(armor)))
+        .with_header(HEADER_ALGORITHM, ALGORITHM_ED25519)
+        .with_header(HEADER_SIGNED_LABEL, &armor.label)
+        .with_header(HEADER_KEY_ID, &key.public_key().fingerprint().key_id().to_string())
+}
+
+/// Verify `armor` against the signature blocks in `signatures`: one with a
+/// matching `Signed-Label` (and `Key-Id`, if present) must verify under `key`.
+pub fn verify_armor(armor: &Armor, signatures: &[Armor], key: &Ed25519PublicKey) -> Result<(), String> {
+    let message = signed_message(armor);
+    let key_id = key.fingerprint().key_id().to_string();
+    let mut found = false;
+    for sig in signatures {
+        if sig.label != LABEL_SIGNATURE
+            || sig.header(HEADER_SIGNED_LABEL) != Some(armor.label.as_str())
+            || sig.header(HEADER_KEY_ID).is_some_and(|id| !id.eq_ignore_ascii_case(&key_id))
+        {
+            continue;
+        }
+        if sig.header(HEADER_ALGORITHM) != Some(ALGORITHM_ED25519) {
+            return Err(format!("unsupported signature algorithm {:?}", sig.header(HEADER_ALGORITHM)));
+        }
+        found = true;
+        if key.verify(&message, &sig.data).is_ok() {
+            return Ok(());
+        }
+    }
+    if found {
+        Err(format!("signature over '{}' does not verify: file altered or wrong signer", armor.label))
+    } else {
+        Err(format!("no signature by key {key_id} for '{}'", armor.label))
+    }
+}
+
+/// Sign the `label` blocks of each `(path, label)` and write all
+/// signatures to `sig_path`.
+#[cfg(feature = "std")]
+pub fn sign_armor_files(
+    files: &[(&str, &str)],
+    key: &Ed25519SecretKey,
+    sig_path: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut sigs = Vec::with_capacity(files.len());
+    for (path, label) in files {
+        sigs.push(sign_armor(&read_armor_file(path, label)?, key));
+    }
+    write_armor_file_all(sig_path, &sigs)
+}
+
+/// Verify the `label` block of `path` against the signatures in `sig_path`,
+/// returning the verified block.
+#[cfg(feature = "std")]
+pub fn verify_armor_file(
+    path: &str,
+    label: &str,
+    sig_path: &str,
+    key: &Ed25519PublicKey,
+) -> Result<Armor, Box<dyn std::error::Error>> {
+    let armor = read_armor_file(path, label)?;
+    verify_armor(&armor, &read_armor_file_all(sig_path)?, key)?;
+    Ok(armor)
+}
+
+fn signed_message(armor: &Armor) -> Vec<u8> {
+    fn lp(out: &mut Vec<u8>, bytes: &[u8]) {
+        out.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+        out.extend_from_slice(bytes);
+    }
+    let headers: Vec<_> = armor
+        .headers
+        .iter()
+        .filter(|(k, _)| ![HEADER_VERSION, HEADER_FEC_PARITY, HEADER_FEC_LENGTH].contains(&k.as_str()))
+        .collect();
+    let mut out = Vec::with_capacity(SIGNATURE_CONTEXT.len() + armor.data.len() + 64);
+    out.extend_from_slice(SIGNATURE_CONTEXT);
+    lp(&mut out, armor.label.as_bytes());
+    out.extend_from_slice(&(headers.len() as u32).to_be_bytes());
+    for (k, v) in headers {
+        lp(&mut out, k.as_bytes());
+        lp(&mut out, v.as_bytes());
+    }
+    lp(&mut out, &armor.data);
+    out
+}
@@ -0,0 +1,179 @@
+//! stego.rs — hide a byte payload inside ordinary cover text
+//!
+//! Two carriers, both invisible when the text is rendered:
+//!
+//! - `ZeroWidth`: after each ASCII space of the cover, one payload byte as
+//!   8 zero-width characters, MSB first (U+200B ZWSP = 0, U+200C ZWNJ = 1).
+//!   ZWJ is not used: it legitimately joins emoji sequences (👨‍👩‍👧), which
+//!   must survive untouched.
+//! - `TrailingWhitespace`: at the end of each line, one payload byte as
+//!   8 characters, MSB first (space = 0, tab = 1). Existing trailing spaces
+//!   and tabs of the cover are removed first.
+//!
+//! The payload is framed by a 2-byte big-endian length, so at most 65535
+//! bytes fit and the capacity of a cover is (slots - 2) bytes. Use
+//! [`capacity`] to check a cover before embedding.
+
+extern crate alloc;
+
+use alloc::{format, string::String, vec::Vec};
+
+const ZWSP: char = '\u{200B}';
+const ZWNJ: char = '\u{200C}';
+
+/// Bytes of length framing in front of the payload.
+const LEN_BYTES: usize = 2;
+
+/// Where the payload bits go in the cover text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Carrier {
+    /// Zero-width characters after each ASCII space (one byte per space).
+    ZeroWidth,
+    /// Spaces/tabs at the end of each line (one byte per line).
+    TrailingWhitespace,
+}
+
+/// What a cover text can carry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Capacity {
+    /// Slots in the cover (spaces or lines); each holds one byte.
+    pub slots: usize,
+    /// Largest payload that fits, after the length framing.
+    pub bytes: usize,
+}
+
+/// Report how many payload bytes `cover` can carry with `carrier`.
+pub fn capacity(cover: &str, carrier: Carrier) -> Capacity {
+    let slots = match carrier {
+        Carrier::ZeroWidth => cover.matches(' ').count(),
+        Carrier::TrailingWhitespace => lines(cover).count(),
+    };
+    let bytes = slots.saturating_sub(LEN_BYTES).min(u16::MAX as usize);
+    Capacity { slots, bytes }
+}
+
+/// Hide `payload` in `cover`. Fails if the cover is too small or (for
+/// `ZeroWidth`) already contains the carrier characters.
+pub fn embed(cover: &str, payload: &[u8], carrier: Carrier) -> Result<String, String> {
+    let cap = capacity(cover, carrier);
+    if payload.len() > cap.bytes {
+        return Err(format!(
+            "payload of {} bytes does not fit: cover carries {} bytes ({} slots)",
+            payload.len(),
+            cap.bytes,
+            cap.slots
+        ));
+    }
+    let mut framed = Vec::with_capacity(LEN_BYTES + payload.len());
+    framed.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+    framed.extend_from_slice(payload);
+
+    match carrier {
+        Carrier::ZeroWidth => {
+            if cover.contains([ZWSP, ZWNJ]) {
+                return Err("cover text already contains zero-width characters".into());
+            }
+            let mut bytes = framed.iter();
+            let mut out = String::with_capacity(cover.len() + framed.len() * 8 * ZWSP.len_utf8());
+            for c in cover.chars() {
+                out.push(c);
+                if c == ' '
+                    && let Some(&b) = bytes.next()
+                {
+                    push_bits(&mut out, b, ZWSP, ZWNJ);
+                }
+            }
+            Ok(out)
+        }
+        Carrier::TrailingWhitespace => {
+            let mut bytes = framed.iter();
+            let mut out = String::with_capacity(cover.len() + framed.len() * 8);
+            for (line, eol) in lines(cover) {
+                out.push_str(line.trim_end_matches([' ', '\t']));
+                if let Some(&b) = bytes.next() {
+                    push_bits(&mut out, b, ' ', '\t');
+                }
+                out.push_str(eol);
+            }
+            Ok(out)
+        }
+    }
+}
+
+/// Recover the payload hidden by [`embed`] with the same `carrier`.
+pub fn extract(text: &str, carrier: Carrier) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    match carrier {
+        Carrier::ZeroWidth => {
+            let bits: Vec<bool> = text
+                .chars()
+                .filter(|&c| c == ZWSP || c == ZWNJ)
+                .map(|c| c == ZWNJ)
+                .collect();
+            if !bits.len().is_multiple_of(8) {
+                return Err(format!("{} zero-width characters is not a whole number of bytes", bits.len()));
+            }
+            bytes.extend(bits.chunks(8).map(byte_from_bits));
+        }
+        Carrier::TrailingWhitespace => {
+            for (n, (line, _)) in lines(text).enumerate() {
+                let trimmed = line.trim_end_matches([' ', '\t']);
+                let tail = &line[trimmed.len()..];
+                match tail.len() {
+                    0 => break,
+                    8 => {
+                        let bits: Vec<bool> = tail.chars().map(|c| c == '\t').collect();
+                        bytes.push(byte_from_bits(&bits));
+                    }
+                    len => {
+                        return Err(format!("line {}: {len} trailing whitespace characters, expected 8", n + 1));
+                    }
+                }
+            }
+        }
+    }
+
+    if bytes.len() < LEN_BYTES {
+        return Err("no hidden payload found".into());
+    }
+    let len = u16::from_be_bytes([bytes[0], bytes[1]]) as usize;
+    let payload = &bytes[LEN_BYTES..];
+    if payload.len() < len {
+        return Err(format!("payload truncated: header says {len} bytes, found {}", payload.len()));
+    }
+    if payload.len() > len {
+        return Err(format!("{} unexpected bytes after the payload", payload.len() - len));
+    }
+    Ok(payload.to_vec())
+}
+
+/// Remove the carrier characters, giving back the cover text
+/// (for `TrailingWhitespace`, without any trailing spaces/tabs).
+pub fn strip(text: &str, carrier: Carrier) -> String {
+    match carrier {
+        Carrier::ZeroWidth => text.chars().filter(|&c| c != ZWSP && c != ZWNJ).collect(),
+        Carrier::TrailingWhitespace => lines(text)
+            .flat_map(|(line, eol)| [line.trim_end_matches([' ', '\t']), eol])
+            .collect(),
+    }
+}
+
+/// Lines of `text` as (content, line ending). '\r\n' and '\n' endings are kept;
+/// an empty segment after a final newline is not a line.
+fn lines(text: &str) -> impl Iterator<Item = (&str, &str)> {
+    text.split_inclusive('\n').map(|seg| {
+        let content = seg.strip_suffix('\n').unwrap_or(seg);
+        let content = content.strip_suffix('\r').unwrap_or(content);
+        (content, &seg[content.len()..])
+    })
+}
+
+fn push_bits(out: &mut String, b: u8, zero: char, one: char) {
+    for i in (0..8).rev() {
+        out.push(if (b >> i) & 1 == 1 { one } else { zero });
+    }
+}
+
+fn byte_from_bits(bits: &[bool]) -> u8 {
+    bits.iter().fold(0, |acc, &bit| (acc << 1) | bit as u8)
+}
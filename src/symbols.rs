@@ -0,0 +1,180 @@
+//! symbols.rs — bytes as emoji / Unicode symbols
+//!
+//! For passing masked payloads (e.g. `PipelineV2Result::rotated_bytes`)
+//! through chat systems that mangle long digit runs and Base64.
+//!
+//! Each symbol carries a fixed number of bits (MSB first) and is taken from
+//! one contiguous block of code points, so every table is fixed and
+//! single-code-point:
+//!
+//! | set          | bits | main symbols        | tail symbols        |
+//! |--------------|------|---------------------|---------------------|
+//! | `Emoji`      | 8    | U+1F400 – U+1F4FF   | —                   |
+//! | `Cjk`        | 10   | U+4E00 – U+51FF     | U+5200 – U+5203     |
+//! | `Hangul`     | 11   | U+AC00 – U+B3FF     | U+B400 – U+B407     |
+//!
+//! The last group is padded with zero bits. When that padding would be 8 bits
+//! or more (the decoder could not tell how many bytes there were), the last
+//! group is instead written as one *tail* symbol that holds only the
+//! remaining bits.
+//!
+//! On decode, whitespace and U+FE0F (emoji variation selector, often added
+//! by chat clients) are ignored; every other character must be in the table.
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
+use crate::ascii_codec::{DecodeError, DecodeErrorKind};
+
+/// Emoji presentation selector, ignored on decode.
+const VS16: char = '\u{FE0F}';
+
+/// Symbol tables for [`encode_symbols`] / [`decode_symbols`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SymbolSet {
+    /// 256 emoji (animals, food, objects), one per byte.
+    Emoji,
+    /// 1024 CJK ideographs, 10 bits per symbol.
+    Cjk,
+    /// 2048 Hangul syllables, 11 bits per symbol.
+    Hangul,
+}
+
+impl SymbolSet {
+    /// Bits carried by one (non-tail) symbol.
+    pub fn bits(&self) -> u32 {
+        match self {
+            SymbolSet::Emoji => 8,
+            SymbolSet::Cjk => 10,
+            SymbolSet::Hangul => 11,
+        }
+    }
+
+    /// Stable lowercase name, suitable for headers and CLI flags.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SymbolSet::Emoji => "emoji",
+            SymbolSet::Cjk => "cjk",
+            SymbolSet::Hangul => "hangul",
+        }
+    }
+
+    /// Inverse of [`SymbolSet::name`] (case-insensitive).
+    pub fn from_name(name: &str) -> Option<SymbolSet> {
+        [SymbolSet::Emoji, SymbolSet::Cjk, SymbolSet::Hangul]
+            .into_iter()
+            .find(|s| s.name().eq_ignore_ascii_case(name))
+    }
+
+    fn base(&self) -> u32 {
+        match self {
+            SymbolSet::Emoji => 0x1F400,
+            SymbolSet::Cjk => 0x4E00,
+            SymbolSet::Hangul => 0xAC00,
+        }
+    }
+
+    /// Main symbols, followed by 2^(bits-8) tail symbols (none for 8 bits).
+    fn table_len(&self) -> u32 {
+        let bits = self.bits();
+        if bits == 8 { 256 } else { (1 << bits) + (1 << (bits - 8)) }
+    }
+
+    fn symbol(&self, index: u32) -> char {
+        char::from_u32(self.base() + index).expect("symbol tables hold valid scalars")
+    }
+
+    fn index(&self, c: char) -> Option<u32> {
+        let i = (c as u32).checked_sub(self.base())?;
+        (i < self.table_len()).then_some(i)
+    }
+}
+
+/// Encode bytes as symbols from `set`, `per_line` symbols per line,
+/// separated by '\n' (no trailing newline).
+pub fn encode_symbols(input: &[u8], set: SymbolSet, per_line: usize) -> String {
+    assert!(per_line >= 1, "per_line must be >= 1");
+
+    let bits = set.bits();
+    let mask = (1u32 << bits) - 1;
+    let mut symbols = Vec::with_capacity((input.len() * 8).div_ceil(bits as usize));
+    let mut acc: u32 = 0;
+    let mut acc_bits = 0;
+    for &b in input {
+        acc = (acc << 8) | b as u32;
+        acc_bits += 8;
+        while acc_bits >= bits {
+            acc_bits -= bits;
+            symbols.push(set.symbol((acc >> acc_bits) & mask));
+        }
+        acc &= (1 << acc_bits) - 1;
+    }
+    if acc_bits > 0 {
+        if bits - acc_bits >= 8 {
+            // padding would look like another byte: emit a tail symbol instead
+            symbols.push(set.symbol((1 << bits) + acc));
+        } else {
+            symbols.push(set.symbol((acc << (bits - acc_bits)) & mask));
+        }
+    }
+
+    let mut out = String::with_capacity(symbols.len() * 4 + symbols.len() / per_line);
+    for (i, c) in symbols.into_iter().enumerate() {
+        if i > 0 && i % per_line == 0 {
+            out.push('\n');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Decode text produced by [`encode_symbols`] with the same `set`.
+/// Unknown symbols, a tail symbol before the end and non-zero padding bits
+/// are reported with their location.
+pub fn decode_symbols(input: &str, set: SymbolSet) -> Result<Vec<u8>, DecodeError> {
+    let bits = set.bits();
+    let main = 1u32 << bits;
+    let mut out = Vec::new();
+    let mut acc: u32 = 0;
+    let mut acc_bits = 0;
+    let mut tail_seen = false;
+    let mut last = None;
+    let symbols = input
+        .char_indices()
+        .filter(|&(_, c)| !c.is_whitespace() && c != VS16);
+    for (token, (at, c)) in symbols.enumerate() {
+        let err = |kind| DecodeError::at(kind, input, at, token);
+        let i = set.index(c).ok_or_else(|| err(DecodeErrorKind::InvalidChar))?;
+        if tail_seen {
+            return Err(err(DecodeErrorKind::Padding));
+        }
+        if i < main {
+            acc = (acc << bits) | i;
+            acc_bits += bits;
+        } else {
+            // tail: exactly the bits needed to complete the last byte
+            let need = (8 - acc_bits % 8) % 8;
+            let value = i - main;
+            if need == 0 || need > bits - 8 || value >> need != 0 {
+                return Err(err(DecodeErrorKind::Padding));
+            }
+            acc = (acc << need) | value;
+            acc_bits += need;
+            tail_seen = true;
+        }
+        while acc_bits >= 8 {
+            acc_bits -= 8;
+            out.push((acc >> acc_bits) as u8);
+        }
+        acc &= (1 << acc_bits) - 1;
+        last = Some((at, token));
+    }
+    // padding bits left by the last symbol must be zero
+    if let Some((at, token)) = last
+        && acc != 0
+    {
+        return Err(DecodeError::at(DecodeErrorKind::Padding, input, at, token));
+    }
+    Ok(out)
+}
@@ -1,8 +1,12 @@
+extern crate alloc;
+
+use alloc::{format, string::String, vec::Vec};
+
 /// Copy the UTF-8 bytes of `s` into a fixed array [u8; N].
 /// - If s is longer than N bytes → truncate.
 /// - If shorter → zero-pad the tail.
+///
 /// Returns (data, used_bytes).
-
 pub fn utf8_to_fixed_bytes<const N: usize>(s: &str) -> ([u8; N], usize) {
     let bytes = s.as_bytes();
     let mut out = [0u8; N];
@@ -0,0 +1,201 @@
+use bitmasher::armor::*;
+
+#[test]
+fn test_crc24_check_value() {
+    // CRC-24/OpenPGP check value
+    assert_eq!(crc24(b"123456789"), 0x21CF02);
+    assert_eq!(crc24(b""), 0xB704CE);
+}
+
+#[test]
+fn test_armor_roundtrip_with_headers() {
+    let data: Vec<u8> = (0..200u16).map(|i| (i * 7) as u8).collect();
+    let armor = Armor::new(LABEL_KEY, &data)
+        .with_header(HEADER_COMMENT, "test key: do not use")
+        .with_header(HEADER_KEY_ID, "0123ABCD");
+    let text = armor.encode().unwrap();
+
+    assert!(text.starts_with("-----BEGIN BITMASHER KEY-----\nVersion: bitmasher "));
+    assert!(text.ends_with("\n-----END BITMASHER KEY-----\n"));
+    assert!(text.lines().all(|l| l.len() <= LINE_LEN));
+
+    let back = Armor::decode(&text).unwrap();
+    assert_eq!(back, armor);
+    assert_eq!(back.header(HEADER_KEY_ID), Some("0123ABCD"));
+    assert_eq!(back.header(HEADER_COMMENT), Some("test key: do not use"));
+    assert!(back.expect_label(LABEL_DATA).is_err());
+
+    // empty body still carries a checksum
+    let empty = Armor::new(LABEL_DATA, &[]);
+    assert_eq!(Armor::decode(&empty.encode().unwrap()).unwrap(), empty);
+}
+
+#[test]
+fn test_armor_detects_corruption() {
+    let text = Armor::new(LABEL_DATA, b"some rotated data bytes").encode().unwrap();
+
+    // flip one body character
+    let body_line = text.lines().nth(3).unwrap();
+    let mut chars: Vec<char> = body_line.chars().collect();
+    chars[2] = if chars[2] == 'A' { 'B' } else { 'A' };
+    let corrupted = text.replacen(body_line, &chars.iter().collect::<String>(), 1);
+    let err = Armor::decode(&corrupted).unwrap_err();
+    assert!(err.contains("CRC-24"), "{err}");
+
+    // mismatched END label
+    let bad_end = text.replace("END BITMASHER DATA", "END BITMASHER KEY");
+    assert!(Armor::decode(&bad_end).is_err());
+
+    // missing checksum
+    let no_crc: String = text.lines().filter(|l| !l.starts_with('=')).map(|l| format!("{l}\n")).collect();
+    assert!(Armor::decode(&no_crc).is_err());
+}
+
+#[test]
+fn test_armor_rejects_bad_headers() {
+    assert!(Armor::new(LABEL_KEY, b"x").with_header("Bad:Name", "v").encode().is_err());
+    assert!(Armor::new(LABEL_KEY, b"x").with_header(HEADER_COMMENT, "two\nlines").encode().is_err());
+    assert!(Armor::new(LABEL_KEY, b"x").with_header(HEADER_COMMENT, "trailing ").encode().is_err());
+    assert!(Armor::new(LABEL_KEY, b"x").with_header(HEADER_COMMENT, " leading").encode().is_err());
+}
+
+#[test]
+fn test_armor_header_values_roundtrip() {
+    let a = Armor::new(LABEL_KEY, b"x")
+        .with_header(HEADER_COMMENT, "")
+        .with_header("Note", "a:b:  c");
+    let text = a.encode().unwrap();
+    assert_eq!(Armor::decode(&text).unwrap(), a);
+    assert_eq!(Armor::find(&text, LABEL_KEY).unwrap().header(HEADER_COMMENT), Some(""));
+    assert_eq!(Armor::decode_all(&text).unwrap(), vec![a]);
+}
+
+#[test]
+fn test_decode_all_embedded_blocks() {
+    let key = Armor::new(LABEL_PROTECTED_KEY, b"wrapped key blob").with_header(HEADER_PIPELINE, "v2");
+    let data = Armor::new(LABEL_DATA, b"rotated data").with_header(HEADER_PIPELINE, "v2");
+
+    let mail = format!(
+        "Hi,\n\nhere are the files:\n\n{}\nand the data\n{}\n--\nsent from my phone\n",
+        key.encode().unwrap(),
+        data.encode().unwrap()
+    );
+
+    let blocks = Armor::decode_all(&mail).unwrap();
+    assert_eq!(blocks, vec![key.clone(), data.clone()]);
+    assert_eq!(Armor::find(&mail, LABEL_DATA).unwrap(), data);
+    assert_eq!(Armor::find(&mail, LABEL_PROTECTED_KEY).unwrap(), key);
+    assert!(Armor::find(&mail, LABEL_KEY).is_err());
+
+    // strict single-block decode refuses the surrounding text
+    assert!(Armor::decode(&mail).is_err());
+    assert!(Armor::decode_all("no armor here").unwrap().is_empty());
+}
+
+#[test]
+fn test_decode_all_reports_bad_block() {
+    let good = Armor::new(LABEL_KEY, b"one").encode().unwrap();
+    let bad = Armor::new(LABEL_DATA, b"two").encode().unwrap().replace("-----END BITMASHER DATA-----\n", "");
+    let err = Armor::decode_all(&format!("{good}{bad}")).unwrap_err();
+    assert!(err.starts_with("block 1"), "{err}");
+}
+
+#[test]
+fn test_multi_block_file() {
+    let blocks = vec![Armor::new(LABEL_KEY, b"key bytes"), Armor::new(LABEL_DATA, b"data bytes")];
+    write_armor_file_all("tmp_multi.asc", &blocks).unwrap();
+    assert_eq!(read_armor_file_all("tmp_multi.asc").unwrap(), blocks);
+    assert_eq!(read_armor_file("tmp_multi.asc", LABEL_DATA).unwrap().data, b"data bytes");
+    std::fs::remove_file("tmp_multi.asc").unwrap();
+}
+
+#[test]
+fn test_decode_all_tolerates_crlf_and_reports_lines() {
+    let armor = Armor::new(LABEL_KEY, b"windows edited").with_header(HEADER_COMMENT, "crlf");
+    let crlf = armor.encode().unwrap().replace('\n', " \r\n");
+    assert!(Armor::decode(&crlf).is_err());
+    assert_eq!(Armor::find(&crlf, LABEL_KEY).unwrap(), armor);
+
+    let text = format!("intro\n{}", armor.encode().unwrap().replace("Comment: crlf", "Comment crlf"));
+    let err = Armor::decode_all(&text).unwrap_err();
+    assert!(err.contains("line 4: malformed header line"), "{err}");
+}
+
+#[test]
+fn test_armor_fec_repairs_damaged_lines() {
+    let data: Vec<u8> = (0..600u16).map(|i| (i * 13 + 5) as u8).collect();
+    let armor = Armor::new(LABEL_DATA, &data).with_header(HEADER_COMMENT, "fec");
+    let text = armor.encode_fec(32).unwrap();
+    assert!(text.contains("Fec-Parity: 32\n"));
+
+    // Clean decode strips the FEC headers and reports nothing.
+    let (clean, repair) = Armor::decode_repair(&text).unwrap();
+    assert_eq!(clean, armor);
+    assert!(repair.report.is_clean());
+
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    let body = lines.iter().position(|l| l.is_empty()).unwrap() + 1;
+    // One mistyped character, and a line with a dropped character.
+    let mut chars: Vec<char> = lines[body].chars().collect();
+    chars[10] = if chars[10] == 'A' { 'B' } else { 'A' };
+    lines[body] = chars.into_iter().collect();
+    lines[body + 3].remove(20);
+    let damaged = lines.join("\n") + "\n";
+
+    let (back, repair) = Armor::decode_repair(&damaged).unwrap();
+    assert_eq!(back, armor);
+    assert!(!repair.report.is_clean());
+    assert_eq!(repair.lines, vec![body + 1, body + 4]);
+    assert_eq!(Armor::find(&damaged, LABEL_DATA).unwrap(), armor);
+}
+
+#[test]
+fn test_armor_fec_repairs_one_line_of_single_block_payload() {
+    let key: Vec<u8> = (0..100u8).collect();
+    let armor = Armor::new(LABEL_DATA, &key);
+    let text = armor.encode_fec(16).unwrap();
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    let body = lines.iter().position(|l| l.is_empty()).unwrap() + 1;
+    // every body line, garbled beyond decoding, is repaired on its own
+    for i in 0..3 {
+        let mut damaged = lines.clone();
+        damaged[body + i].replace_range(5..9, "#?!*");
+        let (back, repair) = Armor::decode_repair(&(damaged.join("\n") + "\n")).unwrap();
+        assert_eq!(back, armor, "line {i}");
+        assert_eq!(repair.lines, vec![body + i + 1]);
+    }
+    lines[body].truncate(30);
+    assert_eq!(Armor::decode(&(lines.join("\n") + "\n")).unwrap(), armor);
+}
+
+#[test]
+fn test_armor_fec_rejects_tampered_headers() {
+    let text = Armor::new(LABEL_DATA, &[1u8; 40]).encode_fec(8).unwrap();
+    for (from, to) in [
+        ("Fec-Parity: 8", "Fec-Parity: 0"),
+        ("Fec-Parity: 8", "Fec-Parity: 255"),
+        ("Fec-Parity: 8", "Fec-Parity: 300"),
+        ("Fec-Length: 40", "Fec-Length: 18446744073709551615"),
+    ] {
+        let err = Armor::decode(&text.replace(from, to)).unwrap_err();
+        assert!(err.contains("parity") || err.contains("too long"), "{to}: {err}");
+    }
+}
+
+#[test]
+fn test_armor_fec_rejects_too_much_damage() {
+    let data = vec![0x5Au8; 300];
+    let text = Armor::new(LABEL_DATA, &data).encode_fec(4).unwrap();
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    let body = lines.iter().position(|l| l.is_empty()).unwrap() + 1;
+    for line in &mut lines[body..body + 3] {
+        line.truncate(8);
+    }
+    assert!(Armor::decode(&(lines.join("\n") + "\n")).is_err());
+
+    // Dropping a whole line is not repairable.
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    lines.remove(body);
+    let err = Armor::decode(&(lines.join("\n") + "\n")).unwrap_err();
+    assert!(err.contains("expected"), "{err}");
+}
@@ -0,0 +1,97 @@
+use bitmasher::ascii_codec::*;
+
+const ALL: [Encoding; 7] = [
+    Encoding::Decimal,
+    Encoding::Hex,
+    Encoding::Base32,
+    Encoding::Base64,
+    Encoding::Base64Url,
+    Encoding::Ascii85,
+    Encoding::Z85,
+];
+
+/// RFC 4648 section 10 test vectors.
+#[test]
+fn test_rfc4648_vectors() {
+    let cases = [
+        ("", "", "", ""),
+        ("f", "66", "MY======", "Zg=="),
+        ("fo", "666F", "MZXQ====", "Zm8="),
+        ("foo", "666F6F", "MZXW6===", "Zm9v"),
+        ("foob", "666F6F62", "MZXW6YQ=", "Zm9vYg=="),
+        ("fooba", "666F6F6261", "MZXW6YTB", "Zm9vYmE="),
+        ("foobar", "666F6F626172", "MZXW6YTBOI======", "Zm9vYmFy"),
+    ];
+    for (plain, hex, b32, b64) in cases {
+        let p = plain.as_bytes();
+        assert_eq!(encode_bytes_ascii_with(p, 76, Encoding::Hex), hex);
+        assert_eq!(encode_bytes_ascii_with(p, 76, Encoding::Base32), b32);
+        assert_eq!(encode_bytes_ascii_with(p, 76, Encoding::Base64), b64);
+        assert_eq!(encode_bytes_ascii_with(p, 76, Encoding::Base64Url), b64.trim_end_matches('='));
+        assert_eq!(decode_bytes_ascii_with(&hex.to_lowercase(), Encoding::Hex).unwrap(), p);
+        assert_eq!(decode_bytes_ascii_with(b32, Encoding::Base32).unwrap(), p);
+        assert_eq!(decode_bytes_ascii_with(b64, Encoding::Base64).unwrap(), p);
+        assert_eq!(decode_bytes_ascii_with(b64, Encoding::Base64Url).unwrap(), p);
+    }
+}
+
+/// Known Ascii85 and Z85 vectors.
+#[test]
+fn test_base85_vectors() {
+    let z85_in = [0x86, 0x4F, 0xD2, 0x6F, 0xB5, 0x59, 0xF7, 0x5B];
+    assert_eq!(encode_bytes_ascii_with(&z85_in, 80, Encoding::Z85), "HelloWorld");
+    assert_eq!(decode_bytes_ascii_with("HelloWorld", Encoding::Z85).unwrap(), z85_in);
+
+    assert_eq!(encode_bytes_ascii_with(b"Man ", 80, Encoding::Ascii85), "9jqo^");
+    assert_eq!(encode_bytes_ascii_with(&[0, 0, 0, 0, 1], 80, Encoding::Ascii85), "z!<");
+    assert_eq!(decode_bytes_ascii_with("z!<", Encoding::Ascii85).unwrap(), [0, 0, 0, 0, 1]);
+}
+
+/// Every encoding round-trips arbitrary lengths, with line wrapping and wrapping markers.
+#[test]
+fn test_all_encodings_roundtrip_wrapped() {
+    let data: Vec<u8> = (0..=255u8).chain([0, 0, 0, 0, 255, 255]).collect();
+    for enc in ALL {
+        for len in [0, 1, 2, 3, 4, 5, 7, 31, data.len()] {
+            let d = &data[..len];
+            let s = encode_bytes_ascii_wrapped_with(d, 16, "BEGIN\n", "\nEND", enc);
+            let out = decode_bytes_ascii_wrapped_with(&s, "BEGIN\n", "\nEND", enc).unwrap();
+            assert_eq!(out, d, "{enc:?} len={len}");
+        }
+        assert_eq!(Encoding::from_name(enc.name()), Some(enc));
+    }
+}
+
+/// Every encoding ignores tabs and CRLF line endings, as documented.
+#[test]
+fn test_decode_ignores_crlf_and_tabs() {
+    let data: Vec<u8> = (0..40u8).collect();
+    for enc in ALL {
+        let s = encode_bytes_ascii_with(&data, 8, enc).replace('\n', "\r\n\t");
+        assert_eq!(decode_bytes_ascii_with(&s, enc).unwrap(), data, "{enc:?}");
+    }
+    assert_eq!(decode_bytes_ascii_with("001 002\r\n003\t004\r\n", Encoding::Decimal).unwrap(), [1, 2, 3, 4]);
+}
+
+#[test]
+fn test_decode_rejects_bad_input() {
+    assert!(decode_bytes_ascii_with("ABC", Encoding::Hex).is_err());
+    assert!(decode_bytes_ascii_with("Zg=!", Encoding::Base64).is_err());
+    assert!(decode_bytes_ascii_with("Z", Encoding::Base64).is_err());
+    assert!(decode_bytes_ascii_with("Zh==", Encoding::Base64).is_err()); // non-zero trailing bits
+    assert!(decode_bytes_ascii_with("s8W-\"", Encoding::Ascii85).is_err()); // > u32::MAX
+    assert!(decode_bytes_ascii_with("9jzqo", Encoding::Ascii85).is_err());
+}
+
+#[test]
+fn test_encoded_file_roundtrip() {
+    let data = b"encoded file helpers";
+    write_ascii_file_wrapped_with("tmp_ascii_b64.txt", data, 16, "<<\n", "\n>>", Encoding::Base64).unwrap();
+    let out = read_ascii_file_wrapped_with("tmp_ascii_b64.txt", "<<\n", "\n>>", Encoding::Base64).unwrap();
+    assert_eq!(out, data);
+    std::fs::remove_file("tmp_ascii_b64.txt").unwrap();
+
+    write_ascii_file_with("tmp_ascii_z85.txt", data, 10, Encoding::Z85).unwrap();
+    assert_eq!(read_ascii_file_with("tmp_ascii_z85.txt", Encoding::Z85).unwrap(), data);
+    std::fs::remove_file("tmp_ascii_z85.txt").unwrap();
+}
@@ -1,5 +1,9 @@
 use bitmasher::ascii_codec::*;
 
+#[allow(dead_code)]
+mod common;
+use common::Chunked;
+
 #[test]
 fn test_wrapped_roundtrip() {
     let data = "abc123\u{FF}hi🙂".as_bytes();
@@ -23,3 +27,116 @@ fn test_ascii_file_roundtrip() {
 
     std::fs::remove_file("tmp_ascii.txt").unwrap();
 }
+
+#[test]
+fn test_wrapped_lenient_crlf_tabs_blank_lines() {
+    let data = b"lenient \x00\xFF";
+    let prefix = "BEGIN\n";
+    let suffix = "\nEND";
+    let strict = encode_bytes_ascii_wrapped(data, 4, prefix, suffix);
+
+    // Windows line endings, tabs, blank lines and a trailing newline
+    let edited = format!("\r\n\r\n{}\r\n\r\n", strict.replace('\n', "\r\n").replacen(' ', "\t", 2));
+    assert!(decode_bytes_ascii_wrapped(&edited, prefix, suffix).is_err());
+    assert_eq!(decode_bytes_ascii_wrapped_lenient(&edited, prefix, suffix).unwrap(), data);
+
+    // prefix/suffix given with CRLF also match
+    assert_eq!(
+        decode_bytes_ascii_wrapped_lenient(&edited, "BEGIN\r\n", "\r\nEND\r\n").unwrap(),
+        data
+    );
+}
+
+#[test]
+fn test_wrapped_lenient_error_locations() {
+    let text = "BEGIN\r\n001 002 003\r\n004 2x6 006\r\nEND\r\n";
+    let err = decode_bytes_ascii_wrapped_lenient(text, "BEGIN\n", "\nEND").unwrap_err();
+    assert_eq!(err.kind, DecodeErrorKind::InvalidNumber);
+    assert_eq!((err.line, err.column, err.token), (3, 5, 4));
+    assert_eq!(err.to_string(), "line 3, column 5 (token 4): invalid decimal number");
+
+    let err = decode_bytes_ascii_wrapped_lenient("BEGIN\n001\t0002\nEND", "BEGIN", "END").unwrap_err();
+    assert_eq!(err.kind, DecodeErrorKind::TokenLength);
+    assert_eq!((err.line, err.column, err.token), (2, 5, 1));
+
+    let err = decode_bytes_ascii_wrapped_lenient("BEGIN\n999\nEND", "BEGIN", "END").unwrap_err();
+    assert_eq!(err.kind, DecodeErrorKind::OutOfRange);
+
+    let err = decode_bytes_ascii_wrapped_lenient("\n\nBEGN\n001\nEND", "BEGIN", "END").unwrap_err();
+    assert_eq!((err.kind, err.line, err.column), (DecodeErrorKind::MissingPrefix, 3, 1));
+
+    let err = decode_bytes_ascii_wrapped_lenient("BEGIN\n001 002\nEN", "BEGIN", "END").unwrap_err();
+    assert_eq!(err.kind, DecodeErrorKind::MissingSuffix);
+}
+
+#[test]
+fn test_lenient_file_roundtrip() {
+    let data = b"crlf file";
+    let text = encode_bytes_ascii_wrapped(data, 3, "<<\n", "\n>>").replace('\n', "\r\n") + "\r\n";
+    std::fs::write("tmp_ascii_crlf.txt", text).unwrap();
+    assert!(read_ascii_file_wrapped("tmp_ascii_crlf.txt", "<<\n", "\n>>").is_err());
+    assert_eq!(read_ascii_file_wrapped_lenient("tmp_ascii_crlf.txt", "<<\n", "\n>>").unwrap(), data);
+    std::fs::remove_file("tmp_ascii_crlf.txt").unwrap();
+}
+
+#[test]
+fn test_ascii_wrapped_writer_matches_in_memory() {
+    use std::io::Write;
+    let data: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+    let (prefix, suffix) = ("==BEGIN==\n", "\n==END==");
+    for len in [0, 1, 7, 8, 9, 1000] {
+        for split in [1, 3, 64] {
+            let mut w = AsciiWrappedWriter::new(Vec::new(), 8, prefix, suffix);
+            for part in data[..len].chunks(split) {
+                w.write_all(part).unwrap();
+            }
+            let out = w.finish().unwrap();
+            assert_eq!(String::from_utf8(out).unwrap(), encode_bytes_ascii_wrapped(&data[..len], 8, prefix, suffix));
+        }
+    }
+}
+
+#[test]
+fn test_ascii_wrapped_reader_chunked() {
+    use std::io::Read;
+    let data: Vec<u8> = (0..=255u8).rev().cycle().take(777).collect();
+    let (prefix, suffix) = ("==BEGIN==\n", "\n==END==");
+    let text = encode_bytes_ascii_wrapped(&data, 8, prefix, suffix);
+    for chunk in [1, 2, 5, 4096] {
+        let mut r = AsciiWrappedReader::new(Chunked { data: text.as_bytes(), chunk }, prefix, suffix);
+        let mut out = Vec::new();
+        r.read_to_end(&mut out).unwrap();
+        assert_eq!(out, data, "chunk={chunk}");
+    }
+
+    let empty = encode_bytes_ascii_wrapped(&[], 8, prefix, suffix);
+    let mut out = Vec::new();
+    AsciiWrappedReader::new(empty.as_bytes(), prefix, suffix).read_to_end(&mut out).unwrap();
+    assert!(out.is_empty());
+}
+
+#[test]
+fn test_ascii_wrapped_reader_errors() {
+    use std::io::Read;
+    let read = |text: &str| {
+        let mut out = Vec::new();
+        AsciiWrappedReader::new(Chunked { data: text.as_bytes(), chunk: 3 }, "B\n", "\nE")
+            .read_to_end(&mut out)
+            .map(|_| out)
+    };
+    assert_eq!(read("B\n001 002\nE").unwrap(), [1, 2]);
+    assert!(read("X\n001 002\nE").is_err());
+    assert!(read("B\n001 002\nX").is_err());
+    assert!(read("B\n001 02\nE").is_err());
+    assert!(read("B\n001 256\nE").is_err());
+    assert!(read("B").is_err());
+}
+
+#[test]
+fn test_ascii_wrapped_reader_rejects_unseparated_stream() {
+    use std::io::Read;
+    // an endless run of digits must fail, not buffer forever
+    let mut r = AsciiWrappedReader::new(std::io::repeat(b'1'), "", "");
+    let err = r.read(&mut [0u8; 16]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,73 @@
+//! The `no_std`-friendly entry points that take a caller-supplied RNG.
+
+use bitmasher::interleave::*;
+use bitmasher::keygen::csprng_key_same_len;
+use bitmasher::pipeline_decode_v2::decode_pipeline_v2_bytes;
+use bitmasher::pipeline_v2::process_str_pipeline_v2_with_rng;
+use bitmasher::ppke::{export_key_password_protected_with_rng, import_key_password_protected};
+use bitmasher::secret::SecretBytes;
+use core::num::NonZeroU32;
+
+#[allow(dead_code)]
+mod common;
+use common::XorShift64;
+
+/// Deterministic stand-in for a hardware RNG. NOT cryptographically secure;
+/// the `CryptoRng` marker is for testing only.
+struct TestRng(XorShift64);
+
+impl rand::RngCore for TestRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u64() as u32
+    }
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.0.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl rand::CryptoRng for TestRng {}
+
+#[test]
+fn test_interleave_with_caller_rng() {
+    let s = "café🙂";
+    let a = interleave_with_rng(s, &mut TestRng(XorShift64::new(7)));
+    let b = interleave_with_rng(s, &mut TestRng(XorShift64::new(7)));
+    assert_eq!(a, b, "same seed, same filler");
+    assert_eq!(deinterleave_original_bytes(&a).unwrap(), s);
+
+    let mut rng = TestRng(XorShift64::new(8));
+    let keyed = interleave_with_key_rng(s, b"key", &mut rng);
+    assert_eq!(deinterleave_with_key(&keyed, b"key").unwrap(), s);
+    let bits = interleave_bits_with_rng(s, 2, &mut rng);
+    assert_eq!(deinterleave_bits(&bits, 2).unwrap(), s);
+    let bits = interleave_bits_with_key_rng(s, b"key", &mut rng);
+    assert_eq!(deinterleave_bits_with_key(&bits, b"key").unwrap(), s);
+    let packets = chaff_with_hmac_rng(s, b"key", 2, &mut rng).unwrap();
+    assert_eq!(winnow_with_hmac(&packets, b"key").unwrap(), s);
+}
+
+#[test]
+fn test_pipeline_v2_and_ppke_with_caller_rng() {
+    let mut rng = TestRng(XorShift64::new(0x0000_A110_C000_0001));
+    let salt = Some(b"bitmasher-salt".as_ref());
+    let info = b"bitmasher:pipeline:v2";
+
+    let fwd = process_str_pipeline_v2_with_rng::<64>("in memory 🙂", salt, info, &mut rng);
+    let blob = export_key_password_protected_with_rng(
+        fwd.hkdf_key.expose_secret(), &SecretBytes::from(b"pw"), NonZeroU32::new(1_000), 16, &mut rng,
+    ).unwrap();
+
+    let key = import_key_password_protected(&blob, &SecretBytes::from(b"pw")).unwrap();
+    let back = decode_pipeline_v2_bytes::<64>(key.expose_secret(), fwd.rotated_bytes(), salt, info).unwrap();
+    assert_eq!(back, "in memory 🙂");
+    assert!(decode_pipeline_v2_bytes::<32>(key.expose_secret(), fwd.rotated_bytes(), salt, info).is_err());
+
+    assert_eq!(csprng_key_same_len(b"12345", &mut rng).len(), 5);
+}
@@ -33,8 +33,8 @@ pub fn set_bit(bytes: &mut [u8], i: usize, val: bool) {
     if val { bytes[byte] |= 1 << off; } else { bytes[byte] &= !(1 << off); }
 }
 pub fn mask_tail_mut(out: &mut [u8], bit_len: usize) {
-    let byte_count = (bit_len + 7) / 8;
-    for i in byte_count..out.len() { out[i] = 0; }
+    let byte_count = bit_len.div_ceil(8);
+    for b in out.iter_mut().skip(byte_count) { *b = 0; }
     let rem = bit_len % 8;
     if rem != 0 && byte_count > 0 {
         let mask = ((1u16 << rem) - 1) as u8;
@@ -57,9 +57,9 @@ pub fn rotate_left_ref(input: &[u8], bit_len: usize, k: usize) -> Vec<u8> {
     out
 }
 pub fn assert_tail_masked(buf: &[u8], bit_len: usize) {
-    let byte_count = (bit_len + 7) / 8;
-    for i in byte_count..buf.len() {
-        assert_eq!(buf[i], 0, "non-participating byte must be zero at index {i}");
+    let byte_count = bit_len.div_ceil(8);
+    for (i, &b) in buf.iter().enumerate().skip(byte_count) {
+        assert_eq!(b, 0, "non-participating byte must be zero at index {i}");
     }
     let rem = bit_len % 8;
     if rem != 0 && byte_count > 0 {
@@ -73,3 +73,23 @@ pub fn assert_tail_masked(buf: &[u8], bit_len: usize) {
         }
     }
 }
+
+/// Decode a hex test vector (even length, no separators).
+pub fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+}
+
+/// Reader that returns at most `chunk` bytes per call, e.g. to split
+/// multibyte characters or tokens across reads.
+pub struct Chunked<'a> {
+    pub data: &'a [u8],
+    pub chunk: usize,
+}
+impl std::io::Read for Chunked<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.chunk.min(buf.len()).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
@@ -0,0 +1,86 @@
+use bitmasher::drbg::{HmacDrbg, MAX_REQUEST_BYTES};
+use bitmasher::interleave::*;
+use bitmasher::keygen::csprng_key_same_len;
+use bitmasher::pipeline_decode_v2::decode_pipeline_v2_bytes;
+use bitmasher::pipeline_v2::process_str_pipeline_v2_with_rng;
+use bitmasher::ppke::{export_key_password_protected_with_rng, import_key_password_protected};
+use bitmasher::secret::SecretBytes;
+use core::num::NonZeroU32;
+use rand::RngCore;
+
+#[allow(dead_code)]
+mod common;
+use common::unhex;
+
+#[test]
+fn test_hmac_drbg_cavp_vector() {
+    // NIST CAVP HMAC_DRBG SHA-256, no PR, no reseed, COUNT = 0:
+    // two 1024-bit generate calls, the second one is checked
+    let mut drbg = HmacDrbg::with_nonce(
+        &unhex("ca851911349384bffe89de1cbdc46e6831e44d34a4fb935ee285dd14b71a7488"),
+        &unhex("659ba96c601dc69fc902940805ec0ca8"),
+        b"",
+    );
+    let mut out = [0u8; 128];
+    drbg.generate(&mut out, b"").unwrap();
+    drbg.generate(&mut out, b"").unwrap();
+    assert_eq!(
+        out.to_vec(),
+        unhex(concat!(
+            "e528e9abf2dece54d47c7e75e5fe302149f817ea9fb4bee6f4199697d04d5b89",
+            "d54fbb978a15b5c443c9ec21036d2460b6f73ebad0dc2aba6e624abf07745bc1",
+            "07694bb7547bb0995f70de25d6b29e2d3011bb19d27676c07162c8b5ccde0668",
+            "961df86803482cb37ed6d5c0bb8d50cf1f50d476aa0458bdaba806f48be9dcb8",
+        ))
+    );
+}
+
+#[test]
+fn test_same_seed_same_stream() {
+    let seed = [0x5Au8; 32];
+    let mut a = HmacDrbg::new(&seed, b"golden:keys");
+    let mut b = HmacDrbg::new(&seed, b"golden:keys");
+    let mut c = HmacDrbg::new(&seed, b"golden:chaff");
+    let (x, y, z) = (a.next_u64(), b.next_u64(), c.next_u64());
+    assert_eq!(x, y);
+    assert_ne!(x, z);
+
+    // reseeding and additional input change the stream
+    b.reseed(b"fresh entropy", b"");
+    assert_ne!(a.next_u64(), b.next_u64());
+
+    let mut big = vec![0u8; MAX_REQUEST_BYTES + 1];
+    assert!(a.generate(&mut big, b"").is_err());
+    a.fill_bytes(&mut big); // split into requests
+    assert!(!format!("{a:?}").contains("5A"));
+}
+
+#[test]
+fn test_reproducible_artifacts() {
+    let seed = b"audit seed: 32 bytes of entropy!";
+    let drbg = || HmacDrbg::new(seed, b"bitmasher:golden");
+
+    assert_eq!(
+        csprng_key_same_len(&[0u8; 32], &mut drbg()),
+        csprng_key_same_len(&[0u8; 32], &mut drbg())
+    );
+    assert_eq!(interleave_with_rng("café🙂", &mut drbg()), interleave_with_rng("café🙂", &mut drbg()));
+    let key = b"chaff key";
+    let chaff = chaff_with_hmac_rng("café🙂", key, 2, &mut drbg()).unwrap();
+    assert_eq!(chaff, chaff_with_hmac_rng("café🙂", key, 2, &mut drbg()).unwrap());
+    assert_eq!(winnow_with_hmac(&chaff, key).unwrap(), "café🙂");
+
+    // PPKE salt and nonce: byte-identical blobs that still decrypt
+    let iter = NonZeroU32::new(1_000);
+    let blob = export_key_password_protected_with_rng(b"key material", &SecretBytes::from(b"pw"), iter, 16, &mut drbg()).unwrap();
+    assert_eq!(blob, export_key_password_protected_with_rng(b"key material", &SecretBytes::from(b"pw"), iter, 16, &mut drbg()).unwrap());
+    assert_eq!(import_key_password_protected(&blob, &SecretBytes::from(b"pw")).unwrap().expose_secret(), b"key material");
+
+    // a whole pipeline run, end to end
+    let (salt, info) = (Some(b"salt".as_ref()), b"info");
+    let f1 = process_str_pipeline_v2_with_rng::<64>("golden 🙂", salt, info, &mut drbg());
+    let f2 = process_str_pipeline_v2_with_rng::<64>("golden 🙂", salt, info, &mut drbg());
+    assert_eq!(f1.rotated_bytes(), f2.rotated_bytes());
+    let back = decode_pipeline_v2_bytes::<64>(f1.hkdf_key.expose_secret(), f1.rotated_bytes(), salt, info).unwrap();
+    assert_eq!(back, "golden 🙂");
+}
@@ -0,0 +1,103 @@
+use bitmasher::fec::*;
+
+#[allow(dead_code)]
+mod common;
+use common::XorShift64;
+
+#[test]
+fn fec_clean_roundtrip_various_lengths() {
+    let mut rng = XorShift64::new(0x0000_FEC0_0000_0001);
+    for &parity in &[2usize, 8, 32] {
+        for len in [0usize, 1, 10, 223, 224, 500, 2000] {
+            let mut data = vec![0u8; len];
+            rng.fill_bytes(&mut data);
+            let stream = rs_encode(&data, parity).unwrap();
+            assert_eq!(stream.len(), stream_len(len, parity).unwrap());
+            assert_eq!(&stream[..len], &data[..], "systematic layout");
+            let (out, report) = rs_decode(&stream, len, parity, &[]).unwrap();
+            assert_eq!(out, data);
+            assert!(report.is_clean());
+        }
+    }
+}
+
+#[test]
+fn fec_corrects_errors_and_erasures_within_bound() {
+    let mut rng = XorShift64::new(0x0000_FEC0_0000_0002);
+    for case in 0..200 {
+        let parity = rng.gen_range(2, 33);
+        let len = rng.gen_range(1, 255 - parity);
+        let mut data = vec![0u8; len];
+        rng.fill_bytes(&mut data);
+        let stream = rs_encode(&data, parity).unwrap();
+        let n = stream.len();
+
+        // pick 2e + r <= parity distinct positions
+        let r = rng.gen_range(0, parity + 1);
+        let e = (parity - r) / 2;
+        let mut positions: Vec<usize> = Vec::new();
+        while positions.len() < e + r {
+            let p = rng.gen_range(0, n);
+            if !positions.contains(&p) { positions.push(p); }
+        }
+        let (erased, errors) = positions.split_at(r);
+
+        let mut damaged = stream.clone();
+        for &p in errors {
+            damaged[p] ^= (rng.gen_range(1, 256)) as u8;
+        }
+        for &p in erased {
+            damaged[p] = rng.gen_range(0, 256) as u8;
+        }
+
+        let (out, report) = rs_decode(&damaged, len, parity, erased)
+            .unwrap_or_else(|err| panic!("case {case}: parity={parity} len={len} e={e} r={r}: {err}"));
+        assert_eq!(out, data, "case {case}");
+        for p in errors {
+            assert!(report.corrected.contains(p), "case {case}: error at {p} not reported");
+        }
+    }
+}
+
+#[test]
+fn fec_interleaving_spreads_bursts() {
+    // 6 blocks of 8 parity bytes: a 16-byte burst is at most 3 errors per block
+    let data: Vec<u8> = (0..900u32).map(|i| (i * 13) as u8).collect();
+    let mut stream = rs_encode(&data, 8).unwrap();
+    for b in &mut stream[100..116] { *b ^= 0x5A; }
+    let (out, report) = rs_decode(&stream, data.len(), 8, &[]).unwrap();
+    assert_eq!(out, data);
+    assert_eq!(report.corrected, (100..116).collect::<Vec<_>>());
+}
+
+#[test]
+fn fec_repairs_any_erased_span() {
+    for &parity in &[1usize, 8, 16, 32] {
+        for len in [100usize, 500, 2000] {
+            let data: Vec<u8> = (0..len).map(|i| (i * 31 + 7) as u8).collect();
+            let stream = rs_encode(&data, parity).unwrap();
+            for start in (0..stream.len() - SPREAD_SPAN).step_by(13) {
+                let mut damaged = stream.clone();
+                damaged[start..start + SPREAD_SPAN].fill(0);
+                let erased: Vec<usize> = (start..start + SPREAD_SPAN).collect();
+                let (out, _) = rs_decode(&damaged, len, parity, &erased)
+                    .unwrap_or_else(|e| panic!("parity={parity} len={len} start={start}: {e}"));
+                assert_eq!(out, data);
+            }
+        }
+    }
+}
+
+#[test]
+fn fec_reports_uncorrectable() {
+    let data = vec![7u8; 50];
+    let mut stream = rs_encode(&data, 4).unwrap();
+    // 50 bytes at parity 4 use ceil(48 / 4) = 12 blocks: hit block 0 five times
+    for b in stream.iter_mut().step_by(12).take(5) { *b ^= 1; }
+    // 5 errors in one block with 4 parity bytes: either detected or miscorrected
+    if let Ok((out, _)) = rs_decode(&stream, data.len(), 4, &[]) {
+        assert_ne!(out, data);
+    }
+    assert!(rs_encode(&data, 0).is_err());
+    assert!(rs_decode(&stream[1..], data.len(), 4, &[]).is_err());
+}
@@ -0,0 +1,134 @@
+use bitmasher::armor::*;
+use bitmasher::fingerprint::*;
+use bitmasher::pipeline_decode_v2::{decode_pipeline_v2_from_armor_files, decode_pipeline_v2_from_files};
+use bitmasher::pipeline_v2::{export_pipeline_v2_armor_files, export_pipeline_v2_ascii_files, process_str_pipeline_v2};
+use bitmasher::ppke::{export_key_password_protected, export_key_password_protected_ascii_file};
+use bitmasher::recipient::X25519SecretKey;
+use bitmasher::secret::SecretBytes;
+use bitmasher::signing::Ed25519SecretKey;
+use core::num::NonZeroU32;
+
+#[test]
+fn test_fingerprint_is_stable_and_binds_algorithm() {
+    let key = [0x42u8; 32];
+    let fp = Fingerprint::of_symmetric(&key);
+    assert_eq!(fp, Fingerprint::new(ALGORITHM_SYMMETRIC, &key));
+    assert_eq!(fp.as_bytes().len(), FINGERPRINT_LEN);
+    assert_ne!(fp, Fingerprint::new("X25519", &key));
+    assert_ne!(fp, Fingerprint::of_symmetric(&key[..31]));
+
+    let id = fp.key_id();
+    assert_eq!(id.as_bytes(), &fp.as_bytes()[..KEY_ID_LEN]);
+    let text = id.to_string();
+    assert_eq!(text.len(), 16);
+    assert_eq!(text, text.to_uppercase());
+    assert_eq!(text.parse::<KeyId>().unwrap(), id);
+    assert_eq!(text.to_lowercase().parse::<KeyId>().unwrap(), id);
+    assert!(fp.to_string().starts_with(&text[..4]));
+    assert_eq!(fp.to_string().split(' ').count(), FINGERPRINT_LEN / 2);
+
+    assert!("0123".parse::<KeyId>().is_err());
+    assert!("0123456789ABCDEG".parse::<KeyId>().is_err());
+}
+
+#[test]
+fn test_public_keys_carry_key_id() {
+    let x = X25519SecretKey::generate().public_key();
+    assert_eq!(x.to_armor().header(HEADER_KEY_ID), Some(x.fingerprint().key_id().to_string().as_str()));
+    let ed = Ed25519SecretKey::generate().public_key();
+    assert_eq!(ed.to_armor().header(HEADER_KEY_ID), Some(ed.fingerprint().key_id().to_string().as_str()));
+    assert_ne!(Fingerprint::new("X25519", ed.as_bytes()), ed.fingerprint());
+}
+
+#[test]
+fn test_pipeline_files_record_key_id() {
+    let (salt, info) = (Some(b"salt".as_ref()), b"info".as_slice());
+    let f = process_str_pipeline_v2::<64>("bound", salt, info);
+    let (key, data) = ("tmp_fp_key.asc", "tmp_fp_data.asc");
+    export_pipeline_v2_armor_files(&f, key, data, &SecretBytes::from(b"pw"), NonZeroU32::new(1_000), 16).unwrap();
+
+    let id = Fingerprint::of_symmetric(f.hkdf_key.expose_secret()).key_id().to_string();
+    assert_eq!(read_armor_file(key, LABEL_PROTECTED_KEY).unwrap().header(HEADER_KEY_ID), Some(id.as_str()));
+    assert_eq!(read_armor_file(data, LABEL_DATA).unwrap().header(HEADER_KEY_ID), Some(id.as_str()));
+    assert_eq!(decode_pipeline_v2_from_armor_files::<64>(key, data, &SecretBytes::from(b"pw"), salt, info).unwrap(), "bound");
+
+    std::fs::remove_file(key).ok();
+    std::fs::remove_file(data).ok();
+}
+
+#[test]
+fn test_wrong_key_file_fails_before_pbkdf2() {
+    let (salt, info) = (Some(b"salt".as_ref()), b"info".as_slice());
+    let a = process_str_pipeline_v2::<64>("first", salt, info);
+    let b = process_str_pipeline_v2::<64>("second", salt, info);
+    let (key_a, data_a) = ("tmp_fp_wrong_key_a.asc", "tmp_fp_wrong_data_a.asc");
+    let (key_b, data_b) = ("tmp_fp_wrong_key_b.asc", "tmp_fp_wrong_data_b.asc");
+    export_pipeline_v2_armor_files(&a, key_a, data_a, &SecretBytes::from(b"pw"), NonZeroU32::new(1_000), 16).unwrap();
+    export_pipeline_v2_armor_files(&b, key_b, data_b, &SecretBytes::from(b"pw"), NonZeroU32::new(1_000), 16).unwrap();
+
+    // rejected on the headers alone, not on the decrypted key
+    let err = decode_pipeline_v2_from_armor_files::<64>(key_b, data_a, &SecretBytes::from(b"pw"), salt, info).unwrap_err();
+    let (id_a, id_b) = (
+        Fingerprint::of_symmetric(a.hkdf_key.expose_secret()).key_id(),
+        Fingerprint::of_symmetric(b.hkdf_key.expose_secret()).key_id(),
+    );
+    assert_eq!(err, format!("wrong key file: data needs Key-Id {id_a}, key file has Key-Id {id_b}"));
+
+    // without Key-Id on the key block, the decrypted key is still checked
+    let key_c = "tmp_fp_wrong_key_c.asc";
+    let blob = export_key_password_protected(b.hkdf_key.expose_secret(), &SecretBytes::from(b"pw"), NonZeroU32::new(1_000), 16).unwrap();
+    write_armor_file(key_c, &Armor::new(LABEL_PROTECTED_KEY, &blob)).unwrap();
+    let err = decode_pipeline_v2_from_armor_files::<64>(key_c, data_a, &SecretBytes::from(b"pw"), salt, info).unwrap_err();
+    assert!(err.contains("decrypted key has Key-Id"), "{err}");
+
+    for f in [key_a, data_a, key_b, data_b, key_c] {
+        std::fs::remove_file(f).ok();
+    }
+}
+
+#[test]
+fn test_legacy_files_reject_wrong_key_length_before_pbkdf2() {
+    let (salt, info) = (Some(b"salt".as_ref()), b"info".as_slice());
+    let (prefix, suffix) = ("<<", ">>");
+    let f = process_str_pipeline_v2::<64>("legacy", salt, info);
+    let (key, data) = ("tmp_fp_legacy_key.asc", "tmp_fp_legacy_data.asc");
+    export_key_password_protected_ascii_file(key, &[7u8; 32], &SecretBytes::from(b"pw"), 8, prefix, suffix, NonZeroU32::new(1_000), 16).unwrap();
+    let ascii = bitmasher::ascii_codec::encode_bytes_ascii_wrapped(f.rotated_bytes(), 8, prefix, suffix);
+    std::fs::write(data, ascii).unwrap();
+
+    let err = decode_pipeline_v2_from_files::<64>(key, data, &SecretBytes::from(b"pw"), prefix, suffix, salt, info).unwrap_err();
+    assert_eq!(err, "wrong key file: key is 32 bytes, expected 64");
+
+    std::fs::remove_file(key).ok();
+    std::fs::remove_file(data).ok();
+}
+
+#[test]
+fn test_legacy_files_record_key_id() {
+    let (salt, info) = (Some(b"salt".as_ref()), b"info".as_slice());
+    let (prefix, suffix) = ("<<\n", "\n>>");
+    let pw = SecretBytes::from(b"pw");
+    let a = process_str_pipeline_v2::<64>("first", salt, info);
+    let b = process_str_pipeline_v2::<64>("second", salt, info);
+    let (key_a, data_a) = ("tmp_fp_ascii_key_a.asc", "tmp_fp_ascii_data_a.asc");
+    let (key_b, data_b) = ("tmp_fp_ascii_key_b.asc", "tmp_fp_ascii_data_b.asc");
+    export_pipeline_v2_ascii_files(&a, key_a, data_a, &pw, 8, prefix, suffix, NonZeroU32::new(1_000), 16).unwrap();
+    export_pipeline_v2_ascii_files(&b, key_b, data_b, &pw, 8, prefix, suffix, NonZeroU32::new(1_000), 16).unwrap();
+
+    let id = Fingerprint::of_symmetric(a.hkdf_key.expose_secret()).key_id();
+    assert!(std::fs::read_to_string(data_a).unwrap().starts_with(&format!("{prefix}Key-Id: {id}\n")));
+    assert_eq!(decode_pipeline_v2_from_files::<64>(key_a, data_a, &pw, prefix, suffix, salt, info).unwrap(), "first");
+
+    let err = decode_pipeline_v2_from_files::<64>(key_b, data_a, &pw, prefix, suffix, salt, info).unwrap_err();
+    assert!(err.starts_with("wrong key file: data needs Key-Id"), "{err}");
+
+    // a key file without a Key-Id line is still checked after decryption
+    let key_c = "tmp_fp_ascii_key_c.asc";
+    export_key_password_protected_ascii_file(key_c, b.hkdf_key.expose_secret(), &pw, 8, prefix, suffix, NonZeroU32::new(1_000), 16).unwrap();
+    let err = decode_pipeline_v2_from_files::<64>(key_c, data_a, &pw, prefix, suffix, salt, info).unwrap_err();
+    assert!(err.contains("decrypted key has Key-Id"), "{err}");
+
+    for f in [key_a, data_a, key_b, data_b, key_c] {
+        std::fs::remove_file(f).ok();
+    }
+}
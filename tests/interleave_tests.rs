@@ -1,6 +1,10 @@
 use bitmasher::interleave::*;
 use bitmasher::utf8util::*;
 
+#[allow(dead_code)]
+mod common;
+use common::Chunked;
+
 /// Ensure utf8_to_fixed_bytes copies bytes correctly and pads as expected.
 #[test]
 fn test_utf8_to_fixed_bytes_basic() {
@@ -55,3 +59,222 @@ fn test_deinterleave_invalid_utf8() {
     let res = deinterleave_original_bytes(&bad);
     assert!(res.is_err());
 }
+
+/// Keyed interleave round-trips with the same key.
+#[test]
+fn test_keyed_interleave_roundtrip() {
+    let key = b"0123456789abcdef0123456789abcdef";
+    let s = "café🙂Русский keyed";
+    let inter = interleave_with_key(s, key);
+    assert_eq!(inter.len(), 2 * s.len() - 1);
+    assert_eq!(deinterleave_with_key(&inter, key).unwrap(), s);
+
+    let hex = interleave_with_key_hex(s, key);
+    assert_eq!(deinterleave_with_key_from_hex(&hex, key).unwrap(), s);
+
+    assert_eq!(deinterleave_with_key(&interleave_with_key("", key), key).unwrap(), "");
+    assert_eq!(deinterleave_with_key(&interleave_with_key("x", key), key).unwrap(), "x");
+}
+
+/// Keyed positions are not the fixed even-index layout, and a wrong key
+/// does not recover the original.
+#[test]
+fn test_keyed_interleave_positions_depend_on_key() {
+    let s = "The quick brown fox jumps over the lazy dog";
+    let key = b"key-A";
+    let inter = interleave_with_key(s, key);
+
+    let even: Vec<u8> = inter.iter().step_by(2).copied().collect();
+    assert_ne!(even, s.as_bytes());
+
+    let wrong = deinterleave_with_key(&inter, b"key-B");
+    assert_ne!(wrong.ok().as_deref(), Some(s));
+}
+
+/// Keyed deinterleave rejects even lengths (cannot be 2n-1).
+#[test]
+fn test_keyed_deinterleave_rejects_even_length() {
+    assert!(deinterleave_with_key(&[1, 2, 3, 4], b"k").is_err());
+}
+
+/// Bit-level interleave round-trips for several chaff ratios.
+#[test]
+fn test_bit_interleave_roundtrip() {
+    let s = "café🙂 bits";
+    for c in 0..4 {
+        let bits = interleave_bits_with_random(s, c);
+        let m = s.len() * 8;
+        assert_eq!(bits.bit_len(), m + (m - 1) * c);
+        assert_eq!(deinterleave_bits(&bits, c).unwrap(), s);
+    }
+    assert_eq!(deinterleave_bits(&interleave_bits_with_random("", 1), 1).unwrap(), "");
+    // wrong ratio is detected from the length
+    let bits = interleave_bits_with_random(s, 1);
+    assert!(deinterleave_bits(&bits, 2).is_err());
+}
+
+/// Keyed bit-level interleave round-trips and survives a BitArray rotation cycle.
+#[test]
+fn test_bit_interleave_keyed_with_rotation() {
+    let key = b"bit-level secret";
+    let s = "Hello🙂ØΩЖ";
+    let bits = interleave_bits_with_key(s, key);
+    assert_eq!(bits.bit_len(), 2 * s.len() * 8 - 1);
+
+    let mut arr = bits.to_bitarray::<64>().unwrap();
+    arr.rotate_left(77);
+    arr.rotate_right(77);
+    let back = bitmasher::BitVec::from(&arr);
+    assert_eq!(back, bits);
+
+    assert_eq!(deinterleave_bits_with_key(&back, key).unwrap(), s);
+    assert_ne!(deinterleave_bits_with_key(&back, b"other").ok().as_deref(), Some(s));
+}
+
+/// Chaffing-and-winnowing round-trips with the right key only.
+#[test]
+fn test_chaff_winnow_roundtrip() {
+    let key = b"shared winnow key";
+    let s = "café🙂 winnow";
+    for c in [1usize, 3, WINNOW_FULL_CHAFF] {
+        let packets = chaff_with_hmac(s, key, c).unwrap();
+        assert_eq!(packets.len(), s.len() * (c + 1) * WINNOW_PACKET_LEN);
+        assert_eq!(winnow_with_hmac(&packets, key).unwrap(), s);
+    }
+
+    let packets = chaff_with_hmac(s, key, 1).unwrap();
+    assert!(winnow_with_hmac(&packets, b"wrong key").is_err());
+    assert_eq!(winnow_with_hmac(&[], key).unwrap(), "");
+}
+
+/// Winnowing detects dropped wheat, duplicated wheat and bad framing.
+#[test]
+fn test_winnow_rejects_tampering() {
+    let key = b"shared winnow key";
+    let packets = chaff_with_hmac("ab", key, 1).unwrap();
+
+    // drop the first byte's group
+    assert!(winnow_with_hmac(&packets[2 * WINNOW_PACKET_LEN..], key).is_err());
+
+    // duplicate the first group (and so its wheat)
+    let mut dup = packets[..2 * WINNOW_PACKET_LEN].to_vec();
+    dup.extend_from_slice(&packets);
+    assert!(winnow_with_hmac(&dup, key).is_err());
+
+    // flip a value bit in both packets: no tag verifies, serial has no wheat
+    let mut bad = packets.clone();
+    bad[4] ^= 1;
+    bad[WINNOW_PACKET_LEN + 4] ^= 1;
+    assert!(winnow_with_hmac(&bad, key).is_err());
+
+    assert!(winnow_with_hmac(&packets[..packets.len() - 1], key).is_err());
+}
+
+/// Full chaff puts every byte value in each serial group exactly once;
+/// zero or more than 255 chaff packets per byte are rejected.
+#[test]
+fn test_chaff_full_value_coverage() {
+    let packets = chaff_with_hmac("Hi", b"k", WINNOW_FULL_CHAFF).unwrap();
+    for group in packets.chunks(256 * WINNOW_PACKET_LEN) {
+        let mut values: Vec<u8> = group.chunks(WINNOW_PACKET_LEN).map(|p| p[4]).collect();
+        values.sort_unstable();
+        assert_eq!(values, (0..=255).collect::<Vec<u8>>());
+    }
+    assert!(chaff_with_hmac("Hi", b"k", 0).is_err());
+    assert!(chaff_with_hmac("Hi", b"k", 256).is_err());
+}
+
+/// Streaming writer output deinterleaves with the in-memory function, for any write split.
+#[test]
+fn test_interleave_writer_matches_layout() {
+    use std::io::Write;
+    let s = "café🙂Русский stream";
+    for split in 1..5 {
+        let mut w = InterleaveWriter::new(Vec::new()).with_utf8_validation();
+        for part in s.as_bytes().chunks(split) {
+            w.write_all(part).unwrap();
+        }
+        let out = w.finish().unwrap();
+        assert_eq!(out.len(), 2 * s.len() - 1);
+        assert_eq!(deinterleave_original_bytes(&out).unwrap(), s);
+    }
+}
+
+/// A caller-supplied RNG makes the streaming writer reproducible and
+/// identical to `interleave_with_rng` with the same generator state.
+#[test]
+fn test_interleave_writer_with_rng_is_reproducible() {
+    use bitmasher::drbg::HmacDrbg;
+    use std::io::Write;
+    let s = "café🙂 seeded stream";
+    let expected = interleave_with_rng(s, &mut HmacDrbg::new(&[7u8; 32], b"writer"));
+    for split in 1..5 {
+        let mut w = InterleaveWriter::with_rng(Vec::new(), HmacDrbg::new(&[7u8; 32], b"writer"));
+        for part in s.as_bytes().chunks(split) {
+            w.write_all(part).unwrap();
+        }
+        assert_eq!(w.finish().unwrap(), expected);
+    }
+}
+
+/// Streaming reader recovers the original across arbitrary read boundaries.
+#[test]
+fn test_deinterleave_reader_chunked() {
+    use std::io::Read;
+    let s = "Hello🙂ØΩЖ streaming";
+    let inter = interleave_with_random_bytes(s);
+    for chunk in 1..6 {
+        let mut r = DeinterleaveReader::new(Chunked { data: &inter, chunk }).with_utf8_validation();
+        let mut out = String::new();
+        r.read_to_string(&mut out).unwrap();
+        assert_eq!(out, s);
+    }
+}
+
+/// UTF-8 validation mode rejects invalid and truncated sequences.
+#[test]
+fn test_streaming_utf8_validation_errors() {
+    use std::io::{Read, Write};
+
+    let mut w = InterleaveWriter::new(Vec::new()).with_utf8_validation();
+    assert!(w.write_all(&[0x61, 0xFF]).is_err());
+
+    let mut w = InterleaveWriter::new(Vec::new()).with_utf8_validation();
+    w.write_all(&"🙂".as_bytes()[..2]).unwrap();
+    assert!(w.finish().is_err());
+
+    // "é" truncated to its lead byte, interleaved as [0xC3]
+    let mut r = DeinterleaveReader::new(Chunked { data: &[0x61, 0x00, 0xC3], chunk: 1 })
+        .with_utf8_validation();
+    let mut out = Vec::new();
+    assert!(r.read_to_end(&mut out).is_err());
+
+    // without validation the raw bytes come through
+    let mut r = DeinterleaveReader::new(Chunked { data: &[0x61, 0x00, 0xC3], chunk: 1 });
+    let mut out = Vec::new();
+    r.read_to_end(&mut out).unwrap();
+    assert_eq!(out, [0x61, 0xC3]);
+}
+
+/// Keyed chaff is drawn from the input's own bytes, so printable ASCII
+/// input yields printable ASCII chaff.
+#[test]
+fn test_keyed_interleave_chaff_matches_payload_bytes() {
+    let s = "attack at dawn";
+    let inter = interleave_with_key(s, b"key-A");
+    assert!(inter.iter().all(|b| s.as_bytes().contains(b)));
+    assert_eq!(deinterleave_with_key(&inter, b"key-A").unwrap(), s);
+}
+
+/// Bit-level chaff is fresh per call: same input and key, different chaff.
+#[test]
+fn test_keyed_bit_chaff_differs_between_calls() {
+    let (s, key) = ("same input, same key", b"key-A");
+    let a = interleave_bits_with_key(s, key);
+    let b = interleave_bits_with_key(s, key);
+    assert_ne!(a.as_bytes(), b.as_bytes());
+    assert_eq!(deinterleave_bits_with_key(&a, key).unwrap(), s);
+    assert_eq!(deinterleave_bits_with_key(&b, key).unwrap(), s);
+
+    assert_ne!(interleave_bits_with_random(s, 1).as_bytes(), interleave_bits_with_random(s, 1).as_bytes());
+}
@@ -12,7 +12,7 @@ fn test_key_export_import() {
     let prefix = "BEGIN\n";
     let suffix = "\nEND";
 
-    export_key_ascii_file("tmp_key.asc", &key, 4, prefix, suffix).unwrap();
+    export_key_ascii_file("tmp_key.asc", key.expose_secret(), 4, prefix, suffix).unwrap();
     let imported = import_key_ascii_file("tmp_key.asc", prefix, suffix).unwrap();
 
     assert_eq!(imported, key);
@@ -1,5 +1,9 @@
 use bitmasher::keygen::*;
 
+#[allow(dead_code)]
+mod common;
+use common::unhex;
+
 #[test]
 fn test_csprng_key_same_len_std() {
     let input = b"any input decides length only";
@@ -59,3 +63,98 @@ fn test_hkdf_sha512_length_match() {
 
     assert_eq!(out.len(), input.len());
 }
+
+#[test]
+fn test_hkdf_rfc5869_vectors() {
+    // RFC 5869 A.1 (SHA-256) and A.3 (SHA-256, empty salt/info)
+    let ikm = [0x0bu8; 22];
+    let okm = hkdf(HkdfAlgorithm::Sha256, &ikm, Some(&unhex("000102030405060708090a0b0c")), &unhex("f0f1f2f3f4f5f6f7f8f9"), 42).unwrap();
+    assert_eq!(okm.expose_secret(), unhex("3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"));
+    let okm = hkdf(HkdfAlgorithm::Sha256, &ikm, None, b"", 42).unwrap();
+    assert_eq!(okm.expose_secret(), unhex("8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8"));
+
+    // the same_len helpers are HKDF with out_len = input length
+    let input = b"secret material";
+    assert_eq!(hkdf_sha512_same_len(input, None, b"i"), hkdf(HkdfAlgorithm::Sha512, input, None, b"i", input.len()).unwrap());
+    assert_eq!(hkdf(HkdfAlgorithm::Sha384, input, None, b"i", 100).unwrap().len(), 100);
+}
+
+#[test]
+fn test_hkdf_limit_and_extension() {
+    for alg in [HkdfAlgorithm::Sha256, HkdfAlgorithm::Sha384, HkdfAlgorithm::Sha512] {
+        let max = alg.max_output_len();
+        assert_eq!(max, 255 * alg.hash_len());
+        assert!(hkdf(alg, b"ikm", None, b"info", max).is_ok());
+        assert!(hkdf(alg, b"ikm", None, b"info", max + 1).is_err());
+
+        // within the limit the extension is plain RFC 5869 ...
+        let rfc = hkdf(alg, b"ikm", None, b"info", max).unwrap();
+        assert_eq!(hkdf_extended(alg, b"ikm", None, b"info", max).unwrap(), rfc);
+        // ... past it every segment uses the length-prefixed info
+        let long = hkdf_extended(alg, b"ikm", None, b"info", 2 * max + 10).unwrap();
+        let segment = |i: u32| {
+            let info = [&4u32.to_be_bytes()[..], b"info", b"\0bitmasher:hkdf-ext:", &i.to_be_bytes()].concat();
+            hkdf(alg, b"ikm", None, &info, max).unwrap()
+        };
+        assert_eq!(&long.expose_secret()[..max], segment(0).expose_secret());
+        assert_eq!(&long.expose_secret()[max..2 * max], segment(1).expose_secret());
+        assert_ne!(&long.expose_secret()[..max], rfc.expose_secret());
+        // shorter extended outputs are prefixes of longer ones
+        let mid = hkdf_extended(alg, b"ikm", None, b"info", max + 5).unwrap();
+        assert_eq!(&long.expose_secret()[..max + 5], mid.expose_secret());
+
+        // an info that spells out another call's segment label no longer
+        // reproduces that segment
+        let forged = [&b"info"[..], b"\0bitmasher:hkdf-ext:", &1u32.to_be_bytes()].concat();
+        let other = hkdf_extended(alg, b"ikm", None, &forged, 2 * max).unwrap();
+        assert_ne!(&other.expose_secret()[..max], &long.expose_secret()[max..2 * max]);
+    }
+
+    // large buffers no longer abort the same_len helpers
+    let big = vec![0x42u8; 40_000];
+    assert_eq!(hkdf_sha512_same_len(&big, None, b"big").len(), big.len());
+    assert_eq!(hkdf_key_same_len(&big, None, b"big").len(), big.len());
+}
+
+#[test]
+fn test_key_hierarchy_paths() {
+    let master = MasterKey::new(b"master secret");
+    let rot = master.derive("tenant/a/pipeline/v2/rotation", 32).unwrap();
+    assert_eq!(rot.path(), "tenant/a/pipeline/v2/rotation");
+    assert_eq!(rot.len(), 32);
+
+    // deterministic, and a delegated subtree derives the same keys
+    assert_eq!(master.derive("tenant/a/pipeline/v2/rotation", 32).unwrap().expose_secret(), rot.expose_secret());
+    let tenant = master.subtree("tenant/a").unwrap();
+    assert_eq!(tenant.path(), "tenant/a");
+    let via_subtree = tenant.derive("pipeline/v2/rotation", 32).unwrap();
+    assert_eq!(via_subtree.expose_secret(), rot.expose_secret());
+    assert_eq!(via_subtree.path(), rot.path());
+
+    // labels that would collide under naive concatenation stay distinct
+    let keys = [
+        master.derive("tenant/ab", 32).unwrap(),
+        master.derive("tenant/a/b", 32).unwrap(),
+        master.derive("tenanta/b", 32).unwrap(),
+        master.derive("tenant/b/a", 32).unwrap(),
+    ];
+    for i in 0..keys.len() {
+        for j in i + 1..keys.len() {
+            assert_ne!(keys[i].expose_secret(), keys[j].expose_secret(), "{} vs {}", keys[i].path(), keys[j].path());
+        }
+    }
+
+    // output length is bound in, not just truncated
+    let short = master.derive("tenant/a", 16).unwrap();
+    let long = master.derive("tenant/a", 32).unwrap();
+    assert_ne!(short.expose_secret(), &long.expose_secret()[..16]);
+
+    // algorithm and master secret matter
+    let other = MasterKey::with_algorithm(b"master secret", HkdfAlgorithm::Sha256);
+    assert_ne!(other.derive("tenant/a", 32).unwrap().expose_secret(), long.expose_secret());
+
+    for bad in ["", "/a", "a/", "a//b"] {
+        assert!(master.derive(bad, 32).is_err(), "{bad:?}");
+    }
+    assert!(!format!("{rot:?}").contains(&format!("{:?}", &rot.expose_secret()[..4])));
+}
@@ -0,0 +1,158 @@
+use bitmasher::armor::{Armor, HEADER_KEY_VERSION, LABEL_DATA};
+use bitmasher::keyring::*;
+use bitmasher::pipeline_decode_v2::decode_pipeline_v2_from_keyring_armor_files;
+use bitmasher::pipeline_v2::{export_pipeline_v2_armor_files_with_keyring, process_str_pipeline_v2};
+use bitmasher::secret::SecretBytes;
+use core::num::NonZeroU32;
+use rand::rngs::OsRng;
+
+#[test]
+fn test_rotation_keeps_old_artifacts_readable() {
+    let mut ring = KeyRing::new();
+    assert!(ring.active().is_err());
+
+    let v1 = ring.add(&[1u8; 32], 1_700_000_000).unwrap();
+    let old = ring.interleave_to_armor("first message").unwrap();
+    assert_eq!(old.header(HEADER_KEY_VERSION), Some("1"));
+
+    let v2 = ring.generate(32, 1_700_086_400, &mut OsRng).unwrap();
+    assert_eq!((v1, v2), (1, 2));
+    assert_eq!(ring.get(v1).unwrap().status, KeyStatus::DecryptOnly);
+    assert_eq!(ring.active().unwrap().id, v2);
+
+    let new = ring.interleave_to_armor("second message").unwrap();
+    assert_eq!(new.header(HEADER_KEY_VERSION), Some("2"));
+
+    // each artifact opens with the version it names, across an encode/decode trip
+    let old = Armor::decode(&old.encode().unwrap()).unwrap();
+    assert_eq!(ring.deinterleave_from_armor(&old).unwrap(), "first message");
+    assert_eq!(ring.deinterleave_from_armor(&new).unwrap(), "second message");
+
+    ring.set_status(v1, KeyStatus::Retired).unwrap();
+    assert!(ring.deinterleave_from_armor(&old).unwrap_err().contains("retired"));
+    ring.prune_retired();
+    assert!(ring.deinterleave_from_armor(&old).unwrap_err().contains("not in keyring"));
+    assert_eq!(ring.versions().len(), 1);
+}
+
+#[test]
+fn test_set_status_active_demotes_current() {
+    let mut ring = KeyRing::new();
+    let v1 = ring.add(b"one", 1).unwrap();
+    let v2 = ring.add(b"two", 2).unwrap();
+    ring.set_status(v1, KeyStatus::Active).unwrap();
+    assert_eq!(ring.active().unwrap().key().expose_secret(), b"one");
+    assert_eq!(ring.get(v2).unwrap().status, KeyStatus::DecryptOnly);
+    assert!(ring.set_status(99, KeyStatus::Retired).is_err());
+    assert!(ring.add(&[], 3).is_err());
+
+    // ids are never reused after pruning
+    ring.set_status(v2, KeyStatus::Retired).unwrap();
+    ring.prune_retired();
+    assert_eq!(ring.add(b"three", 3).unwrap(), 3);
+}
+
+#[test]
+fn test_retired_is_final() {
+    let mut ring = KeyRing::new();
+    let v1 = ring.add(b"one", 1).unwrap();
+    ring.add(b"two", 2).unwrap();
+    ring.set_status(v1, KeyStatus::Retired).unwrap();
+    assert!(ring.set_status(v1, KeyStatus::Active).unwrap_err().contains("retired"));
+    assert!(ring.set_status(v1, KeyStatus::DecryptOnly).is_err());
+    ring.set_status(v1, KeyStatus::Retired).unwrap();
+    assert_eq!(ring.active().unwrap().key().expose_secret(), b"two");
+}
+
+#[test]
+fn test_pipeline_v2_files_survive_rotation() {
+    let (salt, info) = (Some(b"salt".as_ref()), b"info".as_slice());
+    let iterations = NonZeroU32::new(1_000);
+    let mut ring = KeyRing::new();
+    ring.generate(32, 1, &mut OsRng).unwrap();
+
+    let old = process_str_pipeline_v2::<64>("before rotation", salt, info);
+    let (old_key, old_data) = ("tmp_ring_v2_key_1.asc", "tmp_ring_v2_data_1.asc");
+    export_pipeline_v2_armor_files_with_keyring(&old, old_key, old_data, &ring, iterations, 16).unwrap();
+    let data = bitmasher::armor::read_armor_file(old_data, LABEL_DATA).unwrap();
+    assert_eq!(data.header(HEADER_KEY_VERSION), Some("1"));
+
+    let v2 = ring.generate(32, 2, &mut OsRng).unwrap();
+    let new = process_str_pipeline_v2::<64>("after rotation", salt, info);
+    let (new_key, new_data) = ("tmp_ring_v2_key_2.asc", "tmp_ring_v2_data_2.asc");
+    export_pipeline_v2_armor_files_with_keyring(&new, new_key, new_data, &ring, iterations, 16).unwrap();
+
+    let decode = |k, d, ring: &KeyRing| decode_pipeline_v2_from_keyring_armor_files::<64>(k, d, ring, salt, info);
+    assert_eq!(decode(old_key, old_data, &ring).unwrap(), "before rotation");
+    assert_eq!(decode(new_key, new_data, &ring).unwrap(), "after rotation");
+    assert!(decode(new_key, old_data, &ring).unwrap_err().starts_with("wrong key file"));
+
+    ring.set_status(1, KeyStatus::Retired).unwrap();
+    assert!(decode(old_key, old_data, &ring).unwrap_err().contains("retired"));
+    assert_eq!(ring.active().unwrap().id, v2);
+
+    for f in [old_key, old_data, new_key, new_data] {
+        std::fs::remove_file(f).ok();
+    }
+}
+
+#[test]
+fn test_decode_needs_key_version_header() {
+    let mut ring = KeyRing::new();
+    ring.add(b"k", 0).unwrap();
+    let bare = Armor::new(LABEL_DATA, b"xx");
+    assert!(ring.deinterleave_from_armor(&bare).unwrap_err().contains(HEADER_KEY_VERSION));
+    let bad = bare.clone().with_header(HEADER_KEY_VERSION, "v1");
+    assert!(ring.deinterleave_from_armor(&bad).is_err());
+
+    // generic hooks pass the selected key through
+    let a = ring.encode_armor(LABEL_DATA, |key| Ok([key, b"!"].concat())).unwrap();
+    let out = ring.decode_armor(&a, |key, data| Ok((key.to_vec(), data.to_vec()))).unwrap();
+    assert_eq!(out, (b"k".to_vec(), b"k!".to_vec()));
+}
+
+#[test]
+fn test_bytes_roundtrip_and_rejects_corruption() {
+    let mut ring = KeyRing::new();
+    ring.add(&[7u8; 16], 10).unwrap();
+    ring.add(&[8u8; 24], 20).unwrap();
+    ring.set_status(1, KeyStatus::Retired).unwrap();
+
+    let bytes = ring.to_bytes().expose_secret().to_vec();
+    let back = KeyRing::from_bytes(&bytes).unwrap();
+    assert_eq!(back.to_bytes().expose_secret(), bytes);
+    assert_eq!(back.get(1).unwrap().created, 10);
+    assert_eq!(back.get(1).unwrap().status, KeyStatus::Retired);
+    assert_eq!(back.active().unwrap().key().expose_secret(), &[8u8; 24]);
+
+    assert!(KeyRing::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(KeyRing::from_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
+    let mut bad_magic = bytes.clone();
+    bad_magic[0] ^= 1;
+    assert!(KeyRing::from_bytes(&bad_magic).is_err());
+}
+
+#[test]
+fn test_debug_does_not_print_keys() {
+    let mut ring = KeyRing::new();
+    ring.add(&[0xAB; 4], 0).unwrap();
+    let dbg = format!("{ring:?}");
+    assert!(dbg.contains("Active"));
+    assert!(!dbg.contains("171"));
+}
+
+#[test]
+fn test_armored_file_roundtrip() {
+    let mut ring = KeyRing::new();
+    ring.generate(32, 100, &mut OsRng).unwrap();
+    ring.generate(32, 200, &mut OsRng).unwrap();
+    let path = "tmp_keyring.asc";
+    ring.save_armor_file(path, &SecretBytes::from(b"pw"), NonZeroU32::new(1000)).unwrap();
+
+    let text = std::fs::read_to_string(path).unwrap();
+    assert!(text.starts_with("-----BEGIN BITMASHER KEYRING-----"));
+    let back = KeyRing::load_armor_file(path, &SecretBytes::from(b"pw")).unwrap();
+    assert_eq!(back.to_bytes(), ring.to_bytes());
+    assert!(KeyRing::load_armor_file(path, &SecretBytes::from(b"wrong")).is_err());
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,82 @@
+use bitmasher::ascii_codec::DecodeErrorKind;
+use bitmasher::paper::{decode_paper, encode_paper};
+use bitmasher::ppke::{export_key_password_protected_paper, import_key_password_protected_paper};
+use bitmasher::secret::SecretBytes;
+use core::num::NonZeroU32;
+
+fn sample() -> Vec<u8> {
+    (0..100u16).map(|i| (i * 37 + 11) as u8).collect()
+}
+
+#[test]
+fn test_paper_roundtrip_and_layout() {
+    let data = sample();
+    let text = encode_paper(&data, 16);
+    assert!(text.starts_with("# bitmasher paper backup v1\n"));
+    assert!(text.contains("\n  1: 0B 30 55 "));
+    assert!(text.contains("\ntotal: 100 "));
+    assert_eq!(decode_paper(&text).unwrap(), data);
+
+    assert_eq!(decode_paper(&encode_paper(&[], 16)).unwrap(), Vec::<u8>::new());
+}
+
+#[test]
+fn test_paper_tolerates_retyping_style() {
+    let data = sample();
+    let text = encode_paper(&data, 16);
+    // lowercase, different grouping, CRLF, blank lines, no comments
+    let retyped: String = text
+        .lines()
+        .filter(|l| !l.starts_with('#'))
+        .map(|l| {
+            let (n, rest) = l.split_once(':').unwrap();
+            let mut parts: Vec<&str> = rest.split_whitespace().collect();
+            let crc = parts.pop().unwrap();
+            format!("{n}:{} {}\r\n\r\n", parts.concat().to_lowercase(), crc.to_lowercase())
+        })
+        .collect();
+    assert_eq!(decode_paper(&retyped).unwrap(), data);
+}
+
+#[test]
+fn test_paper_pinpoints_mistyped_line() {
+    let text = encode_paper(&sample(), 16);
+
+    // typo on data line 3 (text line 5)
+    let line = text.lines().nth(4).unwrap();
+    let typo = line.replacen(&line[5..7], if &line[5..7] == "00" { "01" } else { "00" }, 1);
+    let err = decode_paper(&text.replacen(line, &typo, 1)).unwrap_err();
+    assert_eq!((err.kind, err.line, err.token), (DecodeErrorKind::LineChecksum, 5, 32));
+
+    // skipped line 2
+    let line2 = text.lines().nth(3).unwrap();
+    let err = decode_paper(&text.replacen(&format!("{line2}\n"), "", 1)).unwrap_err();
+    assert_eq!((err.kind, err.line), (DecodeErrorKind::LineNumber, 4));
+
+    // bad hex digit
+    let err = decode_paper(&text.replacen(&line[5..7], "G0", 1)).unwrap_err();
+    assert_eq!((err.kind, err.line), (DecodeErrorKind::InvalidHex, 5));
+
+    // truncated backup
+    let cut: String = text.lines().take(6).map(|l| format!("{l}\n")).collect();
+    assert_eq!(decode_paper(&cut).unwrap_err().kind, DecodeErrorKind::Checksum);
+}
+
+/// A multibyte separator before a bad CRC token must not split a character.
+#[test]
+fn test_paper_bad_crc_after_nbsp() {
+    let err = decode_paper("  1: 73 6F\u{a0}94677Z\ntotal: 2 000000\n").unwrap_err();
+    assert_eq!((err.kind, err.line, err.column), (DecodeErrorKind::LineChecksum, 1, 12));
+}
+
+#[test]
+fn test_ppke_paper_roundtrip() {
+    let key = b"paper backed key material";
+    let text = export_key_password_protected_paper(key, &SecretBytes::from(b"pw"), 16, NonZeroU32::new(1_000), 16).unwrap();
+    assert_eq!(import_key_password_protected_paper(&text, &SecretBytes::from(b"pw")).unwrap().expose_secret(), key);
+
+    let line = text.lines().nth(3).unwrap();
+    let typo = line.replacen(&line[5..7], if &line[5..7] == "00" { "01" } else { "00" }, 1);
+    let err = import_key_password_protected_paper(&text.replacen(line, &typo, 1), &SecretBytes::from(b"pw")).unwrap_err();
+    assert!(err.to_string().starts_with("line 4,"), "{err}");
+}
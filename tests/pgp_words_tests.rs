@@ -0,0 +1,47 @@
+use bitmasher::ascii_codec::*;
+
+const VECTOR_HEX: [u8; 20] = [
+    0xE5, 0x82, 0x94, 0xF2, 0xE9, 0xA2, 0x27, 0x48, 0x6E, 0x8B,
+    0x06, 0x1B, 0x31, 0xCC, 0x52, 0x8F, 0xD7, 0xFA, 0x3F, 0x19,
+];
+const VECTOR_WORDS: &str = "topmost Istanbul Pluto vagabond treadmill Pacific brackish dictator goldfish Medusa \
+afflict bravado chatter revolver Dupont midsummer stopwatch whimsical cowbell bottomless";
+
+#[test]
+fn test_pgp_words_known_vector() {
+    assert_eq!(encode_pgp_words(&VECTOR_HEX, 20), VECTOR_WORDS);
+    assert_eq!(decode_pgp_words(VECTOR_WORDS).unwrap(), VECTOR_HEX);
+}
+
+#[test]
+fn test_pgp_words_roundtrip_all_bytes() {
+    let data: Vec<u8> = (0..=255u8).chain((0..=255u8).rev()).collect();
+    let text = encode_bytes_ascii_with(&data, 8, Encoding::PgpWords);
+    assert_eq!(text.lines().count(), data.len() / 8);
+    assert_eq!(decode_bytes_ascii_with(&text, Encoding::PgpWords).unwrap(), data);
+
+    let wrapped = encode_bytes_ascii_wrapped_with(&data, 6, "WORDS\n", "\nEND", Encoding::PgpWords);
+    assert_eq!(decode_bytes_ascii_wrapped_with(&wrapped, "WORDS\n", "\nEND", Encoding::PgpWords).unwrap(), data);
+}
+
+#[test]
+fn test_pgp_words_tolerates_case_and_spacing() {
+    let messy = "  TOPMOST   istanbul\r\n\tpluto\u{00A0}VAGABOND \n";
+    assert_eq!(decode_pgp_words(messy).unwrap(), [0xE5, 0x82, 0x94, 0xF2]);
+}
+
+#[test]
+fn test_pgp_words_detects_transcription_errors() {
+    // swapped words
+    let err = decode_pgp_words("topmost Pluto Istanbul vagabond").unwrap_err();
+    assert_eq!((err.kind, err.line, err.column, err.token), (DecodeErrorKind::WordParity, 1, 9, 1));
+
+    // dropped word
+    let err = decode_pgp_words("topmost Istanbul\nvagabond treadmill").unwrap_err();
+    assert_eq!((err.kind, err.line, err.column, err.token), (DecodeErrorKind::WordParity, 2, 1, 2));
+
+    // misspelled word
+    let err = decode_pgp_words("topmost Istanbull").unwrap_err();
+    assert_eq!(err.kind, DecodeErrorKind::UnknownWord);
+    assert_eq!(err.to_string(), "line 1, column 9 (token 1): unknown word");
+}
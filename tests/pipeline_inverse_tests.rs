@@ -15,7 +15,7 @@ fn pipeline_inverse_roundtrip_ok() {
     let inverse = invert_pipeline(&forward, salt, info);
 
     // Full round-trip recovery
-    assert_eq!(inverse.truncated, false, "should not be truncated");
+    assert!(!inverse.truncated, "should not be truncated");
     assert_eq!(inverse.restored_original.as_deref(), Some(input));
     assert_eq!(inverse.restored_interleaved, forward.interleaved);
     assert!(inverse.hkdf_matches, "HKDF must match when fully invertible");
@@ -2,6 +2,7 @@ use bitmasher::pipeline_v2::process_str_pipeline_v2;
 use bitmasher::ppke::export_key_password_protected_ascii_file;
 use bitmasher::ascii_codec::encode_bytes_ascii_wrapped;
 use bitmasher::pipeline_decode_v2::decode_pipeline_v2_from_files;
+use bitmasher::secret::SecretBytes;
 
 #[test]
 fn pipeline_v2_roundtrip_ok() {
@@ -15,7 +16,7 @@ fn pipeline_v2_roundtrip_ok() {
 
     // 1) Export HKDF key
     export_key_password_protected_ascii_file(
-        "key_v2.asc", &fwd.hkdf_key, b"pw123",
+        "key_v2.asc", fwd.hkdf_key.expose_secret(), &SecretBytes::from(b"pw123"),
         8, prefix, suffix, None, 16
     ).unwrap();
 
@@ -26,7 +27,7 @@ fn pipeline_v2_roundtrip_ok() {
 
     // Decode back
     let recovered = decode_pipeline_v2_from_files::<128>(
-        "key_v2.asc", "data_v2.asc", b"pw123", prefix, suffix, salt, info
+        "key_v2.asc", "data_v2.asc", &SecretBytes::from(b"pw123"), prefix, suffix, salt, info
     ).unwrap();
 
     assert_eq!(recovered, input);
@@ -34,3 +35,66 @@ fn pipeline_v2_roundtrip_ok() {
     std::fs::remove_file("key_v2.asc").unwrap();
     std::fs::remove_file("data_v2.asc").unwrap();
 }
+
+#[test]
+fn pipeline_v2_armor_roundtrip_ok() {
+    use bitmasher::armor::{read_armor_file, HEADER_PIPELINE, LABEL_DATA};
+    use bitmasher::pipeline_decode_v2::decode_pipeline_v2_from_armor_files;
+    use bitmasher::pipeline_v2::export_pipeline_v2_armor_files;
+    use core::num::NonZeroU32;
+
+    let salt = Some(b"bitmasher-salt".as_ref());
+    let info = b"bitmasher:pipeline:v2";
+    let input = "café🙂 armored";
+
+    let fwd = process_str_pipeline_v2::<64>(input, salt, info);
+    export_pipeline_v2_armor_files(
+        &fwd, "key_v2_armor.asc", "data_v2_armor.asc", &SecretBytes::from(b"pw123"),
+        NonZeroU32::new(1_000), 16
+    ).unwrap();
+
+    let data = read_armor_file("data_v2_armor.asc", LABEL_DATA).unwrap();
+    assert_eq!(data.header(HEADER_PIPELINE), Some("v2"));
+
+    let recovered = decode_pipeline_v2_from_armor_files::<64>(
+        "key_v2_armor.asc", "data_v2_armor.asc", &SecretBytes::from(b"pw123"), salt, info
+    ).unwrap();
+    assert_eq!(recovered, input);
+
+    // swapped files are rejected by label
+    assert!(decode_pipeline_v2_from_armor_files::<64>(
+        "data_v2_armor.asc", "key_v2_armor.asc", &SecretBytes::from(b"pw123"), salt, info
+    ).is_err());
+
+    std::fs::remove_file("key_v2_armor.asc").unwrap();
+    std::fs::remove_file("data_v2_armor.asc").unwrap();
+}
+
+#[test]
+fn pipeline_v2_symbols_roundtrip_ok() {
+    use bitmasher::pipeline_decode_v2::decode_pipeline_v2_from_symbols;
+    use bitmasher::pipeline_v2::export_pipeline_v2_armor_files;
+    use bitmasher::symbols::SymbolSet;
+    use core::num::NonZeroU32;
+
+    let salt = Some(b"bitmasher-salt".as_ref());
+    let info = b"bitmasher:pipeline:v2";
+    let input = "chat-safe 🙂";
+
+    let fwd = process_str_pipeline_v2::<48>(input, salt, info);
+    export_pipeline_v2_armor_files(
+        &fwd, "key_v2_symbols.asc", "data_v2_symbols.asc", &SecretBytes::from(b"pw123"),
+        NonZeroU32::new(1_000), 16
+    ).unwrap();
+
+    for set in [SymbolSet::Emoji, SymbolSet::Cjk, SymbolSet::Hangul] {
+        let text = fwd.rotated_symbols(set, 12);
+        let recovered = decode_pipeline_v2_from_symbols::<48>(
+            "key_v2_symbols.asc", &text, set, &SecretBytes::from(b"pw123"), salt, info
+        ).unwrap();
+        assert_eq!(recovered, input);
+    }
+
+    std::fs::remove_file("key_v2_symbols.asc").unwrap();
+    std::fs::remove_file("data_v2_symbols.asc").unwrap();
+}
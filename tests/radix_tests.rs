@@ -0,0 +1,72 @@
+use bitmasher::ascii_codec::*;
+
+#[allow(dead_code)]
+mod common;
+use common::XorShift64;
+
+#[test]
+fn test_base58_vectors() {
+    let b58 = RadixAlphabet::base58();
+    let cases: [(&[u8], &str); 5] = [
+        (b"", ""),
+        (b"\0", "1"),
+        (b"Hello World!", "2NEpo7TZRRrLZSi2U"),
+        (&[0, 0, 0x28, 0x7f, 0xb4, 0xcd], "11233QC4"),
+        (b"The quick brown fox jumps over the lazy dog.", "USm3fpXnKG5EUBx2ndxBDMPVciP5hGey2Jh4NDv6gmeo1LkMeiKrLJUUBk6Z"),
+    ];
+    for (data, text) in cases {
+        assert_eq!(encode_radix(data, &b58), text);
+        assert_eq!(decode_radix(text, &b58).unwrap(), data);
+        assert_eq!(encode_bytes_ascii_with(data, 80, Encoding::Base58), text);
+    }
+}
+
+#[test]
+fn test_radix_roundtrip_custom_alphabets() {
+    let alphabets = [
+        BASE36_ALPHABET,
+        BASE58_ALPHABET,
+        "01",
+        "23456789CFGHJMPQRVWX", // no look-alikes
+        "αβγδεζηθικλμνξοπρστυφχψω",
+        "🍎🍌🍒🍇🍉🍋🍑",
+    ];
+    let mut rng = XorShift64::new(0x0000_5AD1_0000_0001);
+    for alphabet in alphabets {
+        let a = RadixAlphabet::new(alphabet).unwrap();
+        for _ in 0..50 {
+            let len = rng.gen_range(0, 40);
+            let zeros = rng.gen_range(0, 4);
+            let mut data = vec![0u8; zeros + len];
+            rng.fill_bytes(&mut data[zeros..]);
+            let text = encode_radix(&data, &a);
+            assert!(text.chars().all(|c| alphabet.contains(c)));
+            assert_eq!(decode_radix(&text, &a).unwrap(), data, "alphabet {alphabet}");
+        }
+    }
+}
+
+#[test]
+fn test_radix_whitespace_and_errors() {
+    let b58 = RadixAlphabet::base58();
+    let wrapped = encode_bytes_ascii_with(&[7u8; 40], 10, Encoding::Base58);
+    assert!(wrapped.contains('\n'));
+    assert_eq!(decode_bytes_ascii_with(&wrapped, Encoding::Base58).unwrap(), vec![7u8; 40]);
+
+    // '0' is not Base58; location is reported
+    let err = decode_radix("2NEpo\n7TZ0RR", &b58).unwrap_err();
+    assert_eq!(err.kind, DecodeErrorKind::InvalidChar);
+    assert_eq!((err.line, err.column, err.token), (2, 4, 8));
+
+    assert!(RadixAlphabet::new("a").is_err());
+    assert!(RadixAlphabet::new("abca").is_err());
+    assert!(RadixAlphabet::new("ab c").is_err());
+
+    let text = encode_bytes_ascii_with(b"\0\0Base36", 76, Encoding::Base36);
+    assert!(text.starts_with("00"));
+    assert_eq!(
+        decode_bytes_ascii_with(&text.to_uppercase(), Encoding::Base36).unwrap(),
+        b"\0\0Base36"
+    );
+    assert_eq!(Encoding::from_name("Base58"), Some(Encoding::Base58));
+}
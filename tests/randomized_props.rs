@@ -1,12 +1,13 @@
 // tests/randomized_props.rs
-use bitmasher::BitArray;
+use bitmasher::{BitArray, BitVec};
 
+#[allow(dead_code)]
 mod common;
 use common::{XorShift64, rotate_left_ref, assert_tail_masked};
 
 #[test]
 fn randomized_contiguous_rotation_matches_reference() {
-    let mut rng = XorShift64::new(0xC0FFEE_F00D_F00D);
+    let mut rng = XorShift64::new(0x00C0_FFEE_F00D_F00D);
 
     for n_bytes in 1..=8 {
         for _case in 0..200 {
@@ -406,3 +407,27 @@ fn randomized_modulo_and_edges() {
         }
     }
 }
+
+#[test]
+fn randomized_bitvec_rotation_matches_reference() {
+    let mut rng = XorShift64::new(0x0B17_0EC0_0000_0001);
+
+    for n_bytes in 1..=16 {
+        for _case in 0..100 {
+            let mut input = vec![0u8; n_bytes];
+            rng.fill_bytes(&mut input);
+            let bit_len = rng.gen_range(0, n_bytes * 8 + 1);
+            let k = if bit_len == 0 { 0 } else { rng.gen_range(0, 5 * bit_len + 8) };
+
+            let mut v = BitVec::from_bytes(&input, bit_len);
+            let mut padded = v.as_bytes().to_vec();
+            padded.resize(n_bytes, 0);
+            let expected = rotate_left_ref(&padded, bit_len, k);
+
+            v.rotate_left(k);
+            assert_eq!(v.as_bytes(), &expected[..bit_len.div_ceil(8)], "BitVec ROL mismatch (bit_len={bit_len}, k={k})");
+            v.rotate_right(k);
+            assert_eq!(v, BitVec::from_bytes(&input, bit_len), "BitVec ROL/ROR roundtrip failed (bit_len={bit_len}, k={k})");
+        }
+    }
+}
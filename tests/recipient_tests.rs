@@ -0,0 +1,77 @@
+use bitmasher::armor::{Armor, HEADER_ALGORITHM};
+use bitmasher::drbg::HmacDrbg;
+use bitmasher::pipeline_v2::process_str_pipeline_v2;
+use bitmasher::recipient::*;
+
+#[allow(dead_code)]
+mod common;
+use common::unhex;
+
+#[test]
+fn test_rfc7748_public_key() {
+    // RFC 7748 section 6.1, Alice
+    let sk = X25519SecretKey::from_bytes(&unhex("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a")).unwrap();
+    assert_eq!(
+        sk.public_key().as_bytes().to_vec(),
+        unhex("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a")
+    );
+    assert!(!format!("{sk:?}").contains("77076d0a"));
+    assert!(X25519SecretKey::from_bytes(&[0u8; 31]).is_err());
+}
+
+#[test]
+fn test_seal_to_several_recipients() {
+    let f = process_str_pipeline_v2::<64>("service to service", None, b"info");
+    let (a, b, c) = (X25519SecretKey::generate(), X25519SecretKey::generate(), X25519SecretKey::generate());
+    let sealed = seal_to_recipients(f.hkdf_key.expose_secret(), &[a.public_key(), b.public_key()]).unwrap();
+
+    assert_eq!(open_with_secret_key(&sealed, &a).unwrap(), f.hkdf_key);
+    assert_eq!(open_with_secret_key(&sealed, &b).unwrap(), f.hkdf_key);
+    assert_eq!(open_with_secret_key(&sealed, &c).unwrap_err(), "not sealed to this key");
+
+    // private key survives export/import
+    let a2 = X25519SecretKey::from_bytes(a.to_secret_bytes().expose_secret()).unwrap();
+    assert_eq!(open_with_secret_key(&sealed, &a2).unwrap(), f.hkdf_key);
+}
+
+#[test]
+fn test_tampering_and_bad_input_rejected() {
+    let sk = X25519SecretKey::generate();
+    let sealed = seal_to_recipients(b"wrapped key", &[sk.public_key()]).unwrap();
+    for i in [0, 4, 10, 40, sealed.len() - 1] {
+        let mut bad = sealed.clone();
+        bad[i] ^= 1;
+        assert!(open_with_secret_key(&bad, &sk).is_err(), "byte {i}");
+    }
+    assert!(open_with_secret_key(&sealed[..sealed.len() - 20], &sk).is_err());
+    assert!(seal_to_recipients(b"k", &[]).is_err());
+    // low-order point
+    let zero = X25519PublicKey::from_bytes(&[0u8; 32]).unwrap();
+    assert!(seal_to_recipients(b"k", &[zero]).is_err());
+}
+
+#[test]
+fn test_public_key_armor_and_files() {
+    let sk = X25519SecretKey::generate();
+    let text = sk.public_key().to_armor().encode().unwrap();
+    assert!(text.starts_with("-----BEGIN BITMASHER PUBLIC KEY-----"));
+    assert!(text.contains("Algorithm: X25519\n"));
+    let pk = X25519PublicKey::from_armor(&Armor::decode(&text).unwrap()).unwrap();
+    assert_eq!(pk, sk.public_key());
+    let mut other = pk.to_armor();
+    other.set_header(HEADER_ALGORITHM, "Ed25519");
+    assert!(X25519PublicKey::from_armor(&other).is_err());
+
+    let path = "tmp_sealed.asc";
+    seal_key_armor_file(path, b"file key", &[pk]).unwrap();
+    assert_eq!(open_key_armor_file(path, &sk).unwrap().expose_secret(), b"file key");
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_seal_with_caller_rng_is_reproducible() {
+    let sk = X25519SecretKey::generate_with_rng(&mut HmacDrbg::new(b"recipient seed", b""));
+    let seal = || seal_to_recipients_with_rng(b"k", &[sk.public_key()], &mut HmacDrbg::new(b"seed", b"seal")).unwrap();
+    assert_eq!(seal(), seal());
+    assert_eq!(open_with_secret_key(&seal(), &sk).unwrap().expose_secret(), b"k");
+}
@@ -0,0 +1,47 @@
+use bitmasher::keygen::{hkdf_sha512_same_len, MasterKey};
+use bitmasher::pipeline_v2::process_str_pipeline_v2;
+use bitmasher::ppke::{export_key_password_protected, import_key_password_protected};
+use bitmasher::secret::SecretBytes;
+use core::num::NonZeroU32;
+
+#[test]
+fn test_debug_is_redacted() {
+    let s = SecretBytes::from(b"hunter2-hunter2".as_slice());
+    let dbg = format!("{s:?}");
+    assert_eq!(dbg, "SecretBytes([REDACTED; 15])");
+    assert_eq!(s.expose_secret(), b"hunter2-hunter2");
+
+    // key-bearing results do not leak through Debug either
+    let key = hkdf_sha512_same_len(&[0xEE; 8], None, b"i");
+    assert_eq!(format!("{key:?}"), "SecretBytes([REDACTED; 8])");
+    let derived = MasterKey::new(b"m").derive("a/b", 16).unwrap();
+    assert!(!format!("{derived:?}").contains(&format!("{:?}", derived.expose_secret())));
+}
+
+#[test]
+fn test_equality_and_accessors() {
+    let a = SecretBytes::new(vec![1, 2, 3]);
+    let mut b = SecretBytes::zeroed(3);
+    assert_ne!(a, b);
+    b.expose_secret_mut().copy_from_slice(&[1, 2, 3]);
+    assert_eq!(a, b);
+    assert_ne!(a, SecretBytes::from(vec![1, 2]));
+    assert_eq!(a.len(), 3);
+    assert!(SecretBytes::default().is_empty());
+}
+
+#[test]
+fn test_keys_flow_as_secret_bytes() {
+    let password = SecretBytes::from(b"correct horse".as_slice());
+    let fwd = process_str_pipeline_v2::<32>("secret", None, b"info");
+    let blob = export_key_password_protected(
+        fwd.hkdf_key.expose_secret(),
+        &password,
+        NonZeroU32::new(1_000),
+        16,
+    )
+    .unwrap();
+    let back: SecretBytes = import_key_password_protected(&blob, &password).unwrap();
+    assert_eq!(back, fwd.hkdf_key);
+    assert_eq!(format!("{back:?}"), "SecretBytes([REDACTED; 32])");
+}
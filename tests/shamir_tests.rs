@@ -0,0 +1,77 @@
+use bitmasher::armor::{Armor, HEADER_SHARE_INDEX, HEADER_SHARE_SET, LABEL_KEY_SHARE};
+use bitmasher::pipeline_decode_v2::decode_pipeline_v2_bytes;
+use bitmasher::pipeline_v2::process_str_pipeline_v2;
+use bitmasher::shamir::*;
+
+#[test]
+fn test_any_threshold_subset_recombines() {
+    let secret: Vec<u8> = (0..=255u8).collect();
+    let shares = split_secret(&secret, 3, 5).unwrap();
+    assert_eq!(shares.len(), 5);
+    assert!(shares.iter().all(|s| s.threshold() == 3 && s.count() == 5));
+
+    for a in 0..5 {
+        for b in a + 1..5 {
+            for c in b + 1..5 {
+                let subset = [shares[c].clone(), shares[a].clone(), shares[b].clone()];
+                assert_eq!(combine_shares(&subset).unwrap().expose_secret(), secret.as_slice());
+            }
+        }
+    }
+    // extra shares are fine
+    assert_eq!(combine_shares(&shares).unwrap().expose_secret(), secret.as_slice());
+    // single shares do not carry the secret
+    assert!(shares.iter().all(|s| s.expose_secret() != secret.as_slice()));
+}
+
+#[test]
+fn test_missing_duplicate_and_mixed_shares_rejected() {
+    let shares = split_secret(b"archived key", 3, 5).unwrap();
+    let err = combine_shares(&shares[..2]).unwrap_err();
+    assert!(err.contains("need 3 shares, got 2"), "{err}");
+    let err = combine_shares(&[shares[0].clone(), shares[1].clone(), shares[0].clone()]).unwrap_err();
+    assert!(err.contains("duplicate share 1"), "{err}");
+    assert!(combine_shares(&[]).is_err());
+
+    let other = split_secret(b"archived key", 3, 5).unwrap();
+    let err = combine_shares(&[shares[0].clone(), shares[1].clone(), other[2].clone()]).unwrap_err();
+    assert!(err.contains("different split"), "{err}");
+
+    assert!(split_secret(b"k", 1, 3).is_err());
+    assert!(split_secret(b"k", 4, 3).is_err());
+    assert!(split_secret(b"", 2, 3).is_err());
+    assert_eq!(split_secret(b"k", 255, 255).unwrap().len(), 255);
+}
+
+#[test]
+fn test_share_armor_roundtrip() {
+    let shares = split_secret(b"0123456789abcdef", 2, 3).unwrap();
+    let text = shares[1].to_armor().encode().unwrap();
+    assert!(text.starts_with("-----BEGIN BITMASHER KEY SHARE-----"));
+    assert!(text.contains("Share-Index: 2\n"));
+    assert!(text.contains("Share-Threshold: 2\n"));
+
+    let back = Share::from_armor(&Armor::decode(&text).unwrap()).unwrap();
+    assert_eq!(back, shares[1]);
+    assert_eq!(combine_shares(&[shares[2].clone(), back]).unwrap().expose_secret(), b"0123456789abcdef");
+
+    let mut bad = shares[0].to_armor();
+    bad.set_header(HEADER_SHARE_INDEX, "0");
+    assert!(Share::from_armor(&bad).is_err());
+    let mut bad = shares[0].to_armor();
+    bad.set_header(HEADER_SHARE_SET, "zz");
+    assert!(Share::from_armor(&bad).is_err());
+    assert!(Share::from_armor(&Armor::new(LABEL_KEY_SHARE, b"x")).is_err());
+    assert!(format!("{:?}", shares[0]).contains("REDACTED"));
+}
+
+#[test]
+fn test_split_pipeline_v2_key() {
+    let (salt, info) = (Some(b"salt".as_ref()), b"info");
+    let f = process_str_pipeline_v2::<64>("archive me", salt, info);
+    let shares = split_secret(f.hkdf_key.expose_secret(), 2, 3).unwrap();
+    let key = combine_shares(&[shares[2].clone(), shares[0].clone()]).unwrap();
+    assert_eq!(key, f.hkdf_key);
+    let back = decode_pipeline_v2_bytes::<64>(key.expose_secret(), f.rotated_bytes(), salt, info).unwrap();
+    assert_eq!(back, "archive me");
+}
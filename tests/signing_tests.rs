@@ -0,0 +1,86 @@
+use bitmasher::armor::*;
+use bitmasher::pipeline_decode_v2::decode_pipeline_v2_from_signed_armor_files;
+use bitmasher::pipeline_v2::{export_pipeline_v2_armor_files, process_str_pipeline_v2};
+use bitmasher::secret::SecretBytes;
+use bitmasher::signing::*;
+use core::num::NonZeroU32;
+
+#[allow(dead_code)]
+mod common;
+use common::unhex;
+
+#[test]
+fn test_rfc8032_vector() {
+    // RFC 8032 section 7.1, TEST 1 (empty message)
+    let sk = Ed25519SecretKey::from_seed(&unhex("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60")).unwrap();
+    let pk = sk.public_key();
+    assert_eq!(pk.as_bytes().to_vec(), unhex("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"));
+    let sig = sk.sign(b"");
+    assert_eq!(
+        sig.to_vec(),
+        unhex(concat!(
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155",
+            "5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+        ))
+    );
+    assert!(pk.verify(b"", &sig).is_ok());
+    assert!(pk.verify(b"x", &sig).is_err());
+    assert!(!format!("{sk:?}").contains("9d61b19d"));
+    assert_eq!(Ed25519SecretKey::from_seed(sk.to_secret_bytes().expose_secret()).unwrap().public_key(), pk);
+}
+
+#[test]
+fn test_armor_signature_survives_reencoding_not_edits() {
+    let sk = Ed25519SecretKey::generate();
+    let data = Armor::new(LABEL_DATA, b"rotated bytes").with_header(HEADER_PIPELINE, "v2");
+    let sig = sign_armor(&data, &sk);
+    assert_eq!(sig.header(HEADER_SIGNED_LABEL), Some(LABEL_DATA));
+    let sigs = [Armor::decode(&sig.encode().unwrap()).unwrap()];
+
+    // FEC and Version are transport details
+    let (fec, _) = Armor::decode_repair(&data.encode_fec(16).unwrap()).unwrap();
+    verify_armor(&fec, &sigs, &sk.public_key()).unwrap();
+    let mut other_version = data.clone();
+    other_version.set_header(HEADER_VERSION, "bitmasher 9.9.9");
+    verify_armor(&other_version, &sigs, &sk.public_key()).unwrap();
+
+    let mut edited = data.clone();
+    edited.data[0] ^= 1;
+    assert!(verify_armor(&edited, &sigs, &sk.public_key()).unwrap_err().contains("does not verify"));
+    let mut relabeled = data.clone();
+    relabeled.set_header(HEADER_PIPELINE, "v1");
+    assert!(verify_armor(&relabeled, &sigs, &sk.public_key()).is_err());
+    let stranger = Ed25519SecretKey::generate().public_key();
+    assert!(verify_armor(&data, &sigs, &stranger).is_err());
+    let key_block = Armor::new(LABEL_PROTECTED_KEY, b"k");
+    assert!(verify_armor(&key_block, &sigs, &sk.public_key()).unwrap_err().contains("no signature"));
+
+    let text = sk.public_key().to_armor().encode().unwrap();
+    assert!(text.contains("Algorithm: Ed25519\n"));
+    assert_eq!(Ed25519PublicKey::from_armor(&Armor::decode(&text).unwrap()).unwrap(), sk.public_key());
+}
+
+#[test]
+fn test_signed_pipeline_files() {
+    let (salt, info) = (Some(b"salt".as_ref()), b"info".as_slice());
+    let sk = Ed25519SecretKey::generate();
+    let f = process_str_pipeline_v2::<64>("signed 🙂", salt, info);
+    let (key, data, sig) = ("tmp_sig_key.asc", "tmp_sig_data.asc", "tmp_sig.asc");
+    export_pipeline_v2_armor_files(&f, key, data, &SecretBytes::from(b"pw"), NonZeroU32::new(1_000), 16).unwrap();
+    sign_armor_files(&[(key, LABEL_PROTECTED_KEY), (data, LABEL_DATA)], &sk, sig).unwrap();
+
+    let pk = sk.public_key();
+    let out = decode_pipeline_v2_from_signed_armor_files::<64>(key, data, sig, &pk, &SecretBytes::from(b"pw"), salt, info).unwrap();
+    assert_eq!(out, "signed 🙂");
+    verify_armor_file(data, LABEL_DATA, sig, &pk).unwrap();
+
+    // a replaced data file is named as such
+    let other = process_str_pipeline_v2::<64>("forged", salt, info);
+    write_armor_file(data, &Armor::new(LABEL_DATA, other.rotated_bytes()).with_header(HEADER_PIPELINE, "v2")).unwrap();
+    let err = decode_pipeline_v2_from_signed_armor_files::<64>(key, data, sig, &pk, &SecretBytes::from(b"pw"), salt, info).unwrap_err();
+    assert!(err.starts_with("data file:"), "{err}");
+
+    for p in [key, data, sig] {
+        std::fs::remove_file(p).unwrap();
+    }
+}
@@ -0,0 +1,67 @@
+use bitmasher::pipeline_v2::process_str_pipeline_v2;
+use bitmasher::stego::*;
+
+const COVER: &str = "The quick brown fox jumps over the lazy dog.\r\n\
+    Pack my box with five dozen liquor jugs.   \n\
+    How vexingly quick daft zebras jump!\n\
+    Sphinx of black quartz, judge my vow.\n\
+    Family: 👨‍👩‍👧 waves hello from the other side of the text.\n";
+
+#[test]
+fn test_stego_roundtrip_both_carriers() {
+    let payload = b"rotated!";
+    for carrier in [Carrier::ZeroWidth, Carrier::TrailingWhitespace] {
+        let cap = capacity(COVER, carrier);
+        let payload = &payload[..cap.bytes.min(payload.len())];
+        let text = embed(COVER, payload, carrier).unwrap();
+        assert_eq!(extract(&text, carrier).unwrap(), payload, "{carrier:?}");
+        // visible text unchanged
+        assert_eq!(strip(&text, carrier), strip(COVER, carrier));
+    }
+}
+
+#[test]
+fn test_stego_capacity_report() {
+    let zw = capacity(COVER, Carrier::ZeroWidth);
+    assert_eq!(zw.slots, COVER.matches(' ').count());
+    assert_eq!(zw.bytes, zw.slots - 2);
+
+    let ws = capacity(COVER, Carrier::TrailingWhitespace);
+    assert_eq!(ws, Capacity { slots: 5, bytes: 3 });
+
+    let err = embed(COVER, b"too long", Carrier::TrailingWhitespace).unwrap_err();
+    assert!(err.contains("does not fit"), "{err}");
+    assert_eq!(capacity("no_spaces_here", Carrier::ZeroWidth).bytes, 0);
+}
+
+#[test]
+fn test_stego_carries_pipeline_output() {
+    let fwd = process_str_pipeline_v2::<32>("secret", Some(b"salt".as_ref()), b"info");
+    let cover = "word ".repeat(64);
+    let text = embed(&cover, fwd.rotated_bytes(), Carrier::ZeroWidth).unwrap();
+    assert_eq!(text.chars().filter(|c| !c.is_whitespace() && !c.is_ascii()).count() % 8, 0);
+    assert_eq!(extract(&text, Carrier::ZeroWidth).unwrap(), fwd.rotated_bytes());
+
+    // emoji ZWJ sequences are preserved and not mistaken for payload
+    let text = embed(COVER, b"hi", Carrier::ZeroWidth).unwrap();
+    assert!(text.contains("👨\u{200D}👩\u{200D}👧"));
+}
+
+#[test]
+fn test_stego_detects_damage() {
+    let text = embed(COVER, b"abc", Carrier::TrailingWhitespace).unwrap();
+    // an editor that trims trailing whitespace on one line
+    let damaged: String = text
+        .lines()
+        .enumerate()
+        .map(|(i, l)| if i == 2 { l.trim_end().to_string() } else { l.to_string() })
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert!(extract(&damaged, Carrier::TrailingWhitespace).is_err());
+
+    assert!(extract(COVER, Carrier::ZeroWidth).is_err());
+    let text = embed(COVER, b"abc", Carrier::ZeroWidth).unwrap();
+    assert!(embed(&text, b"x", Carrier::ZeroWidth).is_err());
+    let cut = text.replacen('\u{200B}', "", 1);
+    assert!(extract(&cut, Carrier::ZeroWidth).is_err());
+}
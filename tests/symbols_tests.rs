@@ -0,0 +1,68 @@
+use bitmasher::ascii_codec::DecodeErrorKind;
+use bitmasher::symbols::*;
+
+#[allow(dead_code)]
+mod common;
+use common::XorShift64;
+
+const ALL: [SymbolSet; 3] = [SymbolSet::Emoji, SymbolSet::Cjk, SymbolSet::Hangul];
+
+#[test]
+fn test_symbols_roundtrip_all_lengths() {
+    let mut rng = XorShift64::new(0x0000_E301_0000_0001);
+    for set in ALL {
+        for len in 0..64 {
+            let mut data = vec![0u8; len];
+            rng.fill_bytes(&mut data);
+            let text = encode_symbols(&data, set, 16);
+            let symbols = text.chars().filter(|c| *c != '\n').count();
+            assert_eq!(symbols, (len * 8).div_ceil(set.bits() as usize), "{set:?} len {len}");
+            assert_eq!(decode_symbols(&text, set).unwrap(), data, "{set:?} len {len}");
+        }
+    }
+}
+
+#[test]
+fn test_symbols_known_values() {
+    assert_eq!(encode_symbols(&[0x00, 0x01, 0xFF], SymbolSet::Emoji, 8), "🐀🐁📿");
+    // 4 bytes = 32 bits: three 10-bit symbols, then 2 bits in a tail symbol
+    let cjk = encode_symbols(&[0xFF; 4], SymbolSet::Cjk, 8);
+    assert_eq!(cjk.chars().last(), Some('\u{5203}'));
+    assert_eq!(SymbolSet::from_name("Hangul"), Some(SymbolSet::Hangul));
+}
+
+#[test]
+fn test_symbols_tolerate_chat_mangling() {
+    let data = b"masked payload";
+    let text = encode_symbols(data, SymbolSet::Emoji, 4);
+    // chat clients add variation selectors, CRLF and spaces
+    let mangled: String = text
+        .chars()
+        .flat_map(|c| if c == '\n' { vec!['\r', '\n', ' '] } else { vec![c, '\u{FE0F}'] })
+        .collect();
+    assert_eq!(decode_symbols(&mangled, SymbolSet::Emoji).unwrap(), data);
+}
+
+#[test]
+fn test_symbols_reject_invalid() {
+    let text = encode_symbols(b"abcdef", SymbolSet::Hangul, 3);
+    let mut chars: Vec<char> = text.chars().collect();
+    chars[1] = 'x';
+    let bad: String = chars.iter().collect();
+    let err = decode_symbols(&bad, SymbolSet::Hangul).unwrap_err();
+    assert_eq!(err.kind, DecodeErrorKind::InvalidChar);
+    assert_eq!((err.line, err.column, err.token), (1, 2, 1));
+
+    // a symbol from another set
+    assert!(decode_symbols(&text, SymbolSet::Cjk).is_err());
+
+    // non-zero padding bits in the last symbol ("a" = 8 bits in an 11-bit symbol)
+    let last = char::from_u32(0xAC00 + ((0x61 << 3) | 1)).unwrap();
+    let err = decode_symbols(&last.to_string(), SymbolSet::Hangul).unwrap_err();
+    assert_eq!(err.kind, DecodeErrorKind::Padding);
+
+    // tail symbol not at the end
+    let tail = '\u{5200}';
+    let err = decode_symbols(&format!("丁丁丁{tail}丁"), SymbolSet::Cjk).unwrap_err();
+    assert_eq!(err.kind, DecodeErrorKind::Padding);
+}